@@ -0,0 +1,385 @@
+use crate::api;
+
+use serde::{de, Deserialize, Serialize};
+
+/// The type of action that an [audit log entry](https://discord.com/developers/docs/resources/audit-log#audit-log-entry-object-audit-log-events)
+/// records. Actions this crate doesn't know about yet are kept as `Unknown`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "u16")]
+pub enum AuditLogEvent {
+    GuildUpdate,
+    ChannelCreate,
+    ChannelUpdate,
+    ChannelDelete,
+    ChannelOverwriteCreate,
+    ChannelOverwriteUpdate,
+    ChannelOverwriteDelete,
+    MemberKick,
+    MemberPrune,
+    MemberBanAdd,
+    MemberBanRemove,
+    MemberUpdate,
+    MemberRoleUpdate,
+    MemberMove,
+    MemberDisconnect,
+    BotAdd,
+    RoleCreate,
+    RoleUpdate,
+    RoleDelete,
+    InviteCreate,
+    InviteUpdate,
+    InviteDelete,
+    WebhookCreate,
+    WebhookUpdate,
+    WebhookDelete,
+    EmojiCreate,
+    EmojiUpdate,
+    EmojiDelete,
+    MessageDelete,
+    MessageBulkDelete,
+    MessagePin,
+    MessageUnpin,
+    IntegrationCreate,
+    IntegrationUpdate,
+    IntegrationDelete,
+    StageInstanceCreate,
+    StageInstanceUpdate,
+    StageInstanceDelete,
+    StickerCreate,
+    StickerUpdate,
+    StickerDelete,
+    GuildScheduledEventCreate,
+    GuildScheduledEventUpdate,
+    GuildScheduledEventDelete,
+    ThreadCreate,
+    ThreadUpdate,
+    ThreadDelete,
+    ApplicationCommandPermissionUpdate,
+    AutoModerationRuleCreate,
+    AutoModerationRuleUpdate,
+    AutoModerationRuleDelete,
+    AutoModerationBlockMessage,
+    AutoModerationFlagToChannel,
+    AutoModerationUserCommunicationDisabled,
+    CreatorMonetizationRequestCreated,
+    CreatorMonetizationTermsAccepted,
+    Unknown(u16),
+}
+
+impl From<u16> for AuditLogEvent {
+    fn from(code: u16) -> Self {
+        match code {
+            1 => Self::GuildUpdate,
+            10 => Self::ChannelCreate,
+            11 => Self::ChannelUpdate,
+            12 => Self::ChannelDelete,
+            13 => Self::ChannelOverwriteCreate,
+            14 => Self::ChannelOverwriteUpdate,
+            15 => Self::ChannelOverwriteDelete,
+            20 => Self::MemberKick,
+            21 => Self::MemberPrune,
+            22 => Self::MemberBanAdd,
+            23 => Self::MemberBanRemove,
+            24 => Self::MemberUpdate,
+            25 => Self::MemberRoleUpdate,
+            26 => Self::MemberMove,
+            27 => Self::MemberDisconnect,
+            28 => Self::BotAdd,
+            30 => Self::RoleCreate,
+            31 => Self::RoleUpdate,
+            32 => Self::RoleDelete,
+            40 => Self::InviteCreate,
+            41 => Self::InviteUpdate,
+            42 => Self::InviteDelete,
+            50 => Self::WebhookCreate,
+            51 => Self::WebhookUpdate,
+            52 => Self::WebhookDelete,
+            60 => Self::EmojiCreate,
+            61 => Self::EmojiUpdate,
+            62 => Self::EmojiDelete,
+            72 => Self::MessageDelete,
+            73 => Self::MessageBulkDelete,
+            74 => Self::MessagePin,
+            75 => Self::MessageUnpin,
+            80 => Self::IntegrationCreate,
+            81 => Self::IntegrationUpdate,
+            82 => Self::IntegrationDelete,
+            83 => Self::StageInstanceCreate,
+            84 => Self::StageInstanceUpdate,
+            85 => Self::StageInstanceDelete,
+            90 => Self::StickerCreate,
+            91 => Self::StickerUpdate,
+            92 => Self::StickerDelete,
+            100 => Self::GuildScheduledEventCreate,
+            101 => Self::GuildScheduledEventUpdate,
+            102 => Self::GuildScheduledEventDelete,
+            110 => Self::ThreadCreate,
+            111 => Self::ThreadUpdate,
+            112 => Self::ThreadDelete,
+            121 => Self::ApplicationCommandPermissionUpdate,
+            140 => Self::AutoModerationRuleCreate,
+            141 => Self::AutoModerationRuleUpdate,
+            142 => Self::AutoModerationRuleDelete,
+            143 => Self::AutoModerationBlockMessage,
+            144 => Self::AutoModerationFlagToChannel,
+            145 => Self::AutoModerationUserCommunicationDisabled,
+            150 => Self::CreatorMonetizationRequestCreated,
+            151 => Self::CreatorMonetizationTermsAccepted,
+            _ => Self::Unknown(code),
+        }
+    }
+}
+
+impl From<AuditLogEvent> for u16 {
+    fn from(event: AuditLogEvent) -> Self {
+        use AuditLogEvent::*;
+
+        match event {
+            GuildUpdate => 1,
+            ChannelCreate => 10,
+            ChannelUpdate => 11,
+            ChannelDelete => 12,
+            ChannelOverwriteCreate => 13,
+            ChannelOverwriteUpdate => 14,
+            ChannelOverwriteDelete => 15,
+            MemberKick => 20,
+            MemberPrune => 21,
+            MemberBanAdd => 22,
+            MemberBanRemove => 23,
+            MemberUpdate => 24,
+            MemberRoleUpdate => 25,
+            MemberMove => 26,
+            MemberDisconnect => 27,
+            BotAdd => 28,
+            RoleCreate => 30,
+            RoleUpdate => 31,
+            RoleDelete => 32,
+            InviteCreate => 40,
+            InviteUpdate => 41,
+            InviteDelete => 42,
+            WebhookCreate => 50,
+            WebhookUpdate => 51,
+            WebhookDelete => 52,
+            EmojiCreate => 60,
+            EmojiUpdate => 61,
+            EmojiDelete => 62,
+            MessageDelete => 72,
+            MessageBulkDelete => 73,
+            MessagePin => 74,
+            MessageUnpin => 75,
+            IntegrationCreate => 80,
+            IntegrationUpdate => 81,
+            IntegrationDelete => 82,
+            StageInstanceCreate => 83,
+            StageInstanceUpdate => 84,
+            StageInstanceDelete => 85,
+            StickerCreate => 90,
+            StickerUpdate => 91,
+            StickerDelete => 92,
+            GuildScheduledEventCreate => 100,
+            GuildScheduledEventUpdate => 101,
+            GuildScheduledEventDelete => 102,
+            ThreadCreate => 110,
+            ThreadUpdate => 111,
+            ThreadDelete => 112,
+            ApplicationCommandPermissionUpdate => 121,
+            AutoModerationRuleCreate => 140,
+            AutoModerationRuleUpdate => 141,
+            AutoModerationRuleDelete => 142,
+            AutoModerationBlockMessage => 143,
+            AutoModerationFlagToChannel => 144,
+            AutoModerationUserCommunicationDisabled => 145,
+            CreatorMonetizationRequestCreated => 150,
+            CreatorMonetizationTermsAccepted => 151,
+            Unknown(code) => code,
+        }
+    }
+}
+
+/// A single [change](https://discord.com/developers/docs/resources/audit-log#audit-log-change-object)
+/// made to the target of an audit log entry. The type of the values depends on the `key`, which
+/// is usually the name of the field that was changed on the target, so the values have the same
+/// shape as that field. For example, `name` changes hold strings and can be read with
+/// [`new_value_as::<String>`](AuditLogChange::new_value_as), while `permissions` changes hold
+/// [`Permissions`](api::permissions::Permissions). The `$add` and `$remove` keys list the roles
+/// given to or taken from a member, which can be read with [`AuditLogChange::roles`].
+#[derive(Debug, Deserialize)]
+pub struct AuditLogChange {
+    pub key: String,
+    /// The value before the change, which is omitted when the value was first set
+    pub old_value: Option<serde_json::Value>,
+    /// The value after the change, which is omitted when the value was removed
+    pub new_value: Option<serde_json::Value>,
+}
+
+/// A role that was given to or taken from a member, as listed by `$add` and `$remove` changes.
+#[derive(Debug, Deserialize)]
+pub struct AuditLogRole {
+    pub id: api::misc::Snowflake,
+    pub name: String,
+}
+
+impl AuditLogChange {
+    /// Parses the value before the change, which is `None` when it was omitted or isn't a `T`.
+    pub fn old_value_as<T: de::DeserializeOwned>(&self) -> Option<T> {
+        T::deserialize(self.old_value.as_ref()?).ok()
+    }
+
+    /// Parses the value after the change, which is `None` when it was omitted or isn't a `T`.
+    pub fn new_value_as<T: de::DeserializeOwned>(&self) -> Option<T> {
+        T::deserialize(self.new_value.as_ref()?).ok()
+    }
+
+    /// The roles that were given to a member by an `$add` change, or taken from them by a
+    /// `$remove` change. This is empty for changes with any other key.
+    pub fn roles(&self) -> Vec<AuditLogRole> {
+        match self.key.as_str() {
+            "$add" | "$remove" => self.new_value_as().unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Extra information that is sent with
+/// [certain actions](https://discord.com/developers/docs/resources/audit-log#audit-log-entry-object-optional-audit-entry-info).
+/// Discord sends the counts here as strings.
+#[derive(Debug, Deserialize)]
+pub struct AuditLogEntryInfo {
+    pub application_id: Option<api::misc::Snowflake>,
+    pub auto_moderation_rule_name: Option<String>,
+    pub auto_moderation_rule_trigger_type: Option<String>,
+    pub channel_id: Option<api::misc::Snowflake>,
+    pub count: Option<String>,
+    pub delete_member_days: Option<String>,
+    /// The ID of the overwritten role or member
+    pub id: Option<api::misc::Snowflake>,
+    pub members_removed: Option<String>,
+    pub message_id: Option<api::misc::Snowflake>,
+    pub role_name: Option<String>,
+    /// The type of overwritten entity, where "0" is a role and "1" is a member
+    #[serde(rename(deserialize = "type"))]
+    pub overwrite_type: Option<String>,
+    pub integration_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogEntry {
+    pub id: api::misc::Snowflake,
+    /// The ID of the channel, user, role or other entity that was affected
+    pub target_id: Option<String>,
+    #[serde(default)]
+    pub changes: Vec<AuditLogChange>,
+    /// The user or application that made the change
+    pub user_id: Option<api::misc::Snowflake>,
+    pub action_type: AuditLogEvent,
+    pub options: Option<AuditLogEntryInfo>,
+    pub reason: Option<String>,
+    /// This is only sent with `GUILD_AUDIT_LOG_ENTRY_CREATE`
+    pub guild_id: Option<api::misc::Snowflake>,
+}
+
+/// A page of a guild's [audit log](https://discord.com/developers/docs/resources/audit-log#audit-log-object),
+/// along with the users and threads that are referenced by its entries.
+#[derive(Debug, Deserialize)]
+pub struct AuditLog {
+    pub audit_log_entries: Vec<AuditLogEntry>,
+    #[serde(default)]
+    pub users: Vec<api::user::User>,
+    #[serde(default)]
+    pub threads: Vec<api::channel::Channel>,
+}
+
+/// The most entries that can be fetched from an audit log in a single request
+pub(crate) const AUDIT_LOG_PAGE_LIMIT: u8 = 100;
+
+/// Filters the entries returned when reading a guild's audit log with
+/// [`Guild::audit_log`](api::guild::Guild::audit_log).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AuditLogQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action_type: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u8>,
+}
+
+impl AuditLogQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only returns entries for actions made by this user.
+    pub fn user_id(&mut self, user_id: api::misc::Snowflake) -> &mut Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    /// Only returns entries for this type of action.
+    pub fn action_type(&mut self, action_type: AuditLogEvent) -> &mut Self {
+        self.action_type = Some(action_type.into());
+        self
+    }
+
+    /// Only returns entries older than this entry.
+    pub fn before(&mut self, entry_id: api::misc::Snowflake) -> &mut Self {
+        self.before = Some(entry_id);
+        self
+    }
+
+    /// Only returns entries newer than this entry.
+    pub fn after(&mut self, entry_id: api::misc::Snowflake) -> &mut Self {
+        self.after = Some(entry_id);
+        self
+    }
+
+    /// The number of entries to return, between 1 and 100. Defaults to 50.
+    pub fn limit(&mut self, limit: u8) -> &mut Self {
+        self.limit = Some(limit.clamp(1, AUDIT_LOG_PAGE_LIMIT));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn change(value: serde_json::Value) -> AuditLogChange {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn typed_values() {
+        let name = change(json!({ "key": "name", "old_value": "old", "new_value": "new" }));
+        assert_eq!(name.old_value_as::<String>().as_deref(), Some("old"));
+        assert_eq!(name.new_value_as::<String>().as_deref(), Some("new"));
+        assert_eq!(name.new_value_as::<u64>(), None);
+
+        let permissions = change(json!({ "key": "permissions", "new_value": "2048" }));
+        assert_eq!(
+            permissions.new_value_as::<api::permissions::Permissions>(),
+            Some(api::permissions::Permissions::SEND_MESSAGES)
+        );
+        assert!(permissions.old_value_as::<api::permissions::Permissions>().is_none());
+    }
+
+    #[test]
+    fn roles() {
+        let added = change(json!({
+            "key": "$add",
+            "new_value": [{ "id": "10", "name": "role" }],
+        }));
+        let roles = added.roles();
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].id, 10.into());
+        assert_eq!(roles[0].name, "role");
+
+        let name = change(json!({ "key": "name", "new_value": "new" }));
+        assert!(name.roles().is_empty());
+    }
+}
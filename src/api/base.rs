@@ -1,32 +1,310 @@
-use reqwest::blocking::Client as HttpClient;
+use futures::stream::{self, Stream, StreamExt};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::multipart;
+use reqwest::{Client as HttpClient, Method, RequestBuilder, Response, StatusCode};
 use serde::de;
 use serde;
+use std::future::Future;
+use std::time::Duration;
 
 use crate::api;
 
-pub fn get<T: de::DeserializeOwned>(
+/// Starts a request to a Discord API endpoint that isn't authenticated as the bot, which is used
+/// for webhook endpoints where the token in the URL grants access.
+fn unauthenticated_request(method: Method, endpoint: String) -> RequestBuilder {
+    let url = format!("https://discordapp.com/api/v9/{}", endpoint);
+    HttpClient::new().request(method, url)
+}
+
+/// Starts a request to a Discord API endpoint, authenticated as the bot. When a `reason` is
+/// provided it will be shown alongside the action in the guild's audit log.
+fn request(
     config: &api::config::BotConfig,
+    method: Method,
     endpoint: String,
+    reason: Option<String>,
+) -> RequestBuilder {
+    let builder = unauthenticated_request(method, endpoint)
+        .header(reqwest::header::AUTHORIZATION, format!("Bot {}", config.token));
+    match reason {
+        Some(reason) => builder.header(
+            "X-Audit-Log-Reason",
+            utf8_percent_encode(&reason, NON_ALPHANUMERIC).to_string(),
+        ),
+        None => builder,
+    }
+}
+
+/// Reads how many seconds we should wait before retrying a rate limited request.
+async fn retry_after(response: Response) -> Result<f64, api::misc::RequestError> {
+    #[derive(serde::Deserialize)]
+    struct RateLimited {
+        retry_after: f64,
+    }
+
+    let header = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+    match header {
+        Some(seconds) => Ok(seconds),
+        None => {
+            let body = response.bytes().await?;
+            Ok(serde_json::from_slice::<RateLimited>(&body)?.retry_after)
+        }
+    }
+}
+
+/// When we've used up the last request in a rate limit bucket, we wait for the bucket to reset
+/// so that the next request to it won't be rejected.
+async fn wait_for_exhausted_bucket(response: &Response) {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+    if let (Some(remaining), Some(reset_after)) = (
+        header("X-RateLimit-Remaining"),
+        header("X-RateLimit-Reset-After"),
+    ) {
+        if remaining == 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(reset_after)).await;
+        }
+    }
+}
+
+/// Sends a request and parses the response, returning an error when the request fails, Discord
+/// responds with an error status, or the response can't be parsed. Endpoints that respond with
+/// `204 No Content` can be parsed as `()`.
+///
+/// Requests that are [rate limited](https://discord.com/developers/docs/topics/rate-limits) are
+/// retried once the rate limit has passed, unless their body can't be sent again (such as file
+/// uploads).
+async fn try_send<T: de::DeserializeOwned>(
+    mut builder: RequestBuilder,
+) -> Result<T, api::misc::RequestError> {
+    let response = loop {
+        let retry = builder.try_clone();
+        let response = builder.send().await?;
+        match retry {
+            Some(retry) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                let seconds = retry_after(response).await?;
+                tokio::time::sleep(Duration::from_secs_f64(seconds)).await;
+                builder = retry;
+            }
+            _ => break response,
+        }
+    };
+
+    wait_for_exhausted_bucket(&response).await;
+    let body = response.error_for_status()?.bytes().await?;
+    if body.is_empty() {
+        Ok(serde_json::from_slice(b"null")?)
+    } else {
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+/// Sends a request and parses the response like [`try_send`], panicking when it fails.
+async fn send<T: de::DeserializeOwned>(builder: RequestBuilder) -> T {
+    try_send(builder).await.unwrap()
+}
+
+async fn send_json<T: de::DeserializeOwned, H: serde::Serialize>(
+    builder: RequestBuilder,
+    body: H,
 ) -> T {
-    let url = format!("https://discordapp.com/api/v9/{}", endpoint);
-    return HttpClient::new()
-        .get(url)
-        .header("Authorization", format!("Bot {}", config.token))
-        .send().unwrap()
-        .json().unwrap();
+    send(
+        builder
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_string(&body).unwrap()),
+    )
+    .await
 }
 
-pub fn post<T: de::DeserializeOwned, H: serde::Serialize>(
+pub async fn get<T: de::DeserializeOwned>(
     config: &api::config::BotConfig,
     endpoint: String,
-    body: H
 ) -> T {
-    let url = format!("https://discordapp.com/api/v9/{}", endpoint);
-    HttpClient::new()
-        .post(url)
-        .header(reqwest::header::AUTHORIZATION, format!("Bot {}", config.token))
-        .header(reqwest::header::CONTENT_TYPE, "application/json")
-        .body(serde_json::to_string(&body).unwrap())
-        .send().unwrap()
-        .json().unwrap()
+    send(request(config, Method::GET, endpoint, None)).await
+}
+
+/// Sends a GET request, returning an error instead of panicking when it fails.
+pub async fn try_get<T: de::DeserializeOwned>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+) -> Result<T, api::misc::RequestError> {
+    try_send(request(config, Method::GET, endpoint, None)).await
+}
+
+/// Sends a GET request with `query` serialized as the query string.
+pub async fn get_with_query<T: de::DeserializeOwned, Q: serde::Serialize>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    query: Q,
+) -> T {
+    send(request(config, Method::GET, endpoint, None).query(&query)).await
+}
+
+pub async fn post<T: de::DeserializeOwned, H: serde::Serialize>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    body: H,
+    reason: Option<String>,
+) -> T {
+    send_json(request(config, Method::POST, endpoint, reason), body).await
+}
+
+pub async fn patch<T: de::DeserializeOwned, H: serde::Serialize>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    body: H,
+    reason: Option<String>,
+) -> T {
+    send_json(request(config, Method::PATCH, endpoint, reason), body).await
+}
+
+/// Sends a POST request without a body.
+pub async fn post_empty(config: &api::config::BotConfig, endpoint: String) {
+    send(request(config, Method::POST, endpoint, None).body(Vec::new())).await
+}
+
+pub async fn put<T: de::DeserializeOwned, H: serde::Serialize>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    body: H,
+    reason: Option<String>,
+) -> T {
+    send_json(request(config, Method::PUT, endpoint, reason), body).await
+}
+
+/// Sends a PUT request without a body.
+pub async fn put_empty(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    reason: Option<String>,
+) {
+    send(request(config, Method::PUT, endpoint, reason).body(Vec::new())).await
+}
+
+pub async fn delete<T: de::DeserializeOwned>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    reason: Option<String>,
+) -> T {
+    send(request(config, Method::DELETE, endpoint, reason)).await
+}
+
+fn multipart_form<H: serde::Serialize>(body: H, files: Vec<(String, Vec<u8>)>) -> multipart::Form {
+    let mut form = multipart::Form::new()
+        .text("payload_json", serde_json::to_string(&body).unwrap());
+    for (index, (filename, data)) in files.into_iter().enumerate() {
+        let part = multipart::Part::bytes(data).file_name(filename);
+        form = form.part(format!("files[{}]", index), part);
+    }
+    form
+}
+
+/// Sends a message body alongside a set of files as `multipart/form-data`. The body is sent
+/// as the `payload_json` field and each file as `files[n]`, where `n` is its index in `files`.
+pub async fn post_multipart<T: de::DeserializeOwned, H: serde::Serialize>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    body: H,
+    files: Vec<(String, Vec<u8>)>,
+    reason: Option<String>,
+) -> T {
+    let form = multipart_form(body, files);
+    send(request(config, Method::POST, endpoint, reason).multipart(form)).await
+}
+
+/// Sends a set of text fields alongside a single file as `multipart/form-data`, for endpoints
+/// that take their fields directly rather than as `payload_json`. The file is sent as `file`.
+pub async fn post_form<T: de::DeserializeOwned>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    fields: Vec<(&'static str, String)>,
+    (filename, data): (String, Vec<u8>),
+    reason: Option<String>,
+) -> T {
+    let form = fields
+        .into_iter()
+        .fold(multipart::Form::new(), |form, (name, value)| form.text(name, value))
+        .part("file", multipart::Part::bytes(data).file_name(filename));
+    send(request(config, Method::POST, endpoint, reason).multipart(form)).await
+}
+
+/// The same as [`post_multipart`], but for endpoints that edit an existing resource.
+pub async fn patch_multipart<T: de::DeserializeOwned, H: serde::Serialize>(
+    config: &api::config::BotConfig,
+    endpoint: String,
+    body: H,
+    files: Vec<(String, Vec<u8>)>,
+) -> T {
+    let form = multipart_form(body, files);
+    send(request(config, Method::PATCH, endpoint, None).multipart(form)).await
+}
+
+/// Sends a JSON body to a webhook endpoint, such as `webhooks/{id}/{token}`, without
+/// authenticating as the bot.
+pub async fn webhook_json<T: de::DeserializeOwned, Q: serde::Serialize, H: serde::Serialize>(
+    method: Method,
+    endpoint: String,
+    query: Q,
+    body: H,
+) -> T {
+    send_json(unauthenticated_request(method, endpoint).query(&query), body).await
+}
+
+/// The same as [`webhook_json`], but with files uploaded alongside the body in the same way as
+/// [`post_multipart`].
+pub async fn webhook_multipart<T: de::DeserializeOwned, Q: serde::Serialize, H: serde::Serialize>(
+    method: Method,
+    endpoint: String,
+    query: Q,
+    body: H,
+    files: Vec<(String, Vec<u8>)>,
+) -> T {
+    let form = multipart_form(body, files);
+    send(unauthenticated_request(method, endpoint).query(&query).multipart(form)).await
+}
+
+/// Sends a webhook request without a body, such as deleting a message the webhook sent.
+pub async fn webhook_empty<T: de::DeserializeOwned, Q: serde::Serialize>(
+    method: Method,
+    endpoint: String,
+    query: Q,
+) -> T {
+    send(unauthenticated_request(method, endpoint).query(&query)).await
+}
+
+/// Streams the items of a paginated endpoint, requesting pages as the stream is consumed.
+/// `fetch_page` requests the page at a cursor, starting with `start`, and `next_cursor` returns
+/// the cursor of the page that follows it, or `None` when it was the last page.
+pub fn paginate<'a, C, P, F, Fut, N>(
+    start: C,
+    fetch_page: F,
+    next_cursor: N,
+) -> impl Stream<Item = P::Item> + 'a
+where
+    C: Clone + 'a,
+    P: IntoIterator + 'a,
+    F: Fn(C) -> Fut + 'a,
+    Fut: Future<Output = P> + 'a,
+    N: Fn(C, &P) -> Option<C> + 'a,
+{
+    let pages = stream::unfold(
+        (Some(start), fetch_page, next_cursor),
+        |(cursor, fetch_page, next_cursor)| async move {
+            // A cursor of `None` means the previous page was the last one
+            let cursor = cursor?;
+            let page = fetch_page(cursor.clone()).await;
+            let next = next_cursor(cursor, &page);
+            Some((stream::iter(page), (next, fetch_page, next_cursor)))
+        },
+    );
+    pages.flatten()
 }
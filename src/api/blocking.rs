@@ -0,0 +1,23 @@
+//! Blocking access to the REST API for callers that aren't running inside of an async runtime.
+//!
+//! Every endpoint in [`api`](crate::api) is async. Rather than duplicating each of them, this
+//! module drives any of those futures to completion on a private, single-threaded runtime:
+//!
+//! ```ignore
+//! let channel = api::blocking::run(api::channel::Channel::get(&config, channel_id));
+//! ```
+//!
+//! Like `reqwest::blocking`, this will panic if it is called from within a tokio runtime. Code
+//! running on tokio should `.await` the endpoint directly instead.
+use std::future::Future;
+
+use tokio::runtime;
+
+/// Runs an API future to completion, blocking the current thread until it resolves.
+pub fn run<F: Future>(request: F) -> F::Output {
+    runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Could not build runtime for blocking request")
+        .block_on(request)
+}
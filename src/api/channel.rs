@@ -1,22 +1,78 @@
 use crate::api;
 
-use serde::{Deserialize, Serialize};
-use serde_repr::Deserialize_repr;
+use futures::stream::Stream;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io, path::Path};
 
-#[derive(Debug, Deserialize_repr)]
-#[repr(i64)]
+/// The [type](https://discord.com/developers/docs/resources/channel#channel-object-channel-types)
+/// of a channel. Discord occasionally adds new types, so types this crate doesn't know about yet
+/// are kept as `Unknown`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
 pub enum ChannelType {
-    GuildText = 0,
-    DirectMessage = 1,
-    GuildVoice = 2,
-    GroupDirectMessage = 3,
-    GuildCategory = 4,
-    GuildNews = 5,
-    GuildStore = 6,
-    GuildNewsThread = 10,
-    GuildPublicThread = 11,
-    GuildPrivateThread = 12,
-    GuildStageVoice = 13,
+    GuildText,
+    DirectMessage,
+    GuildVoice,
+    GroupDirectMessage,
+    GuildCategory,
+    GuildNews,
+    GuildStore,
+    GuildNewsThread,
+    GuildPublicThread,
+    GuildPrivateThread,
+    GuildStageVoice,
+    GuildDirectory,
+    GuildForum,
+    /// A channel that can only contain threads, like a forum channel, but is laid out as media
+    GuildMedia,
+    Unknown(u8),
+}
+
+impl From<u8> for ChannelType {
+    fn from(channel_type: u8) -> Self {
+        match channel_type {
+            0 => Self::GuildText,
+            1 => Self::DirectMessage,
+            2 => Self::GuildVoice,
+            3 => Self::GroupDirectMessage,
+            4 => Self::GuildCategory,
+            5 => Self::GuildNews,
+            6 => Self::GuildStore,
+            10 => Self::GuildNewsThread,
+            11 => Self::GuildPublicThread,
+            12 => Self::GuildPrivateThread,
+            13 => Self::GuildStageVoice,
+            14 => Self::GuildDirectory,
+            15 => Self::GuildForum,
+            16 => Self::GuildMedia,
+            _ => Self::Unknown(channel_type),
+        }
+    }
+}
+
+impl From<ChannelType> for u8 {
+    fn from(channel_type: ChannelType) -> Self {
+        match channel_type {
+            ChannelType::GuildText => 0,
+            ChannelType::DirectMessage => 1,
+            ChannelType::GuildVoice => 2,
+            ChannelType::GroupDirectMessage => 3,
+            ChannelType::GuildCategory => 4,
+            ChannelType::GuildNews => 5,
+            ChannelType::GuildStore => 6,
+            ChannelType::GuildNewsThread => 10,
+            ChannelType::GuildPublicThread => 11,
+            ChannelType::GuildPrivateThread => 12,
+            ChannelType::GuildStageVoice => 13,
+            ChannelType::GuildDirectory => 14,
+            ChannelType::GuildForum => 15,
+            ChannelType::GuildMedia => 16,
+            ChannelType::Unknown(channel_type) => channel_type,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -29,6 +85,257 @@ pub struct Channel {
     pub name: Option<String>,
     pub topic: Option<String>,
     pub nsfw: Option<bool>,
+    #[serde(default)]
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    /// The category of a guild channel, or the channel a thread was created in
+    pub parent_id: Option<api::misc::Snowflake>,
+    /// The ID of the last message sent in the channel. The message may no longer exist.
+    pub last_message_id: Option<api::misc::Snowflake>,
+    /// The number of seconds users must wait between sending messages (slowmode)
+    pub rate_limit_per_user: Option<u16>,
+    pub bitrate: Option<u32>,
+    pub user_limit: Option<u16>,
+    /// The voice region of a voice channel, where `None` picks the region automatically
+    pub rtc_region: Option<String>,
+    /// The users in a direct message
+    #[serde(default)]
+    pub recipients: Vec<api::user::User>,
+    /// The creator of a group direct message or thread
+    pub owner_id: Option<api::misc::Snowflake>,
+    pub thread_metadata: Option<ThreadMetadata>,
+    /// The bot's membership in a thread, if it has joined the thread
+    pub member: Option<api::thread::ThreadMember>,
+    /// An approximate count of the users in a thread, which stops counting at 50
+    pub member_count: Option<u32>,
+    /// The number of minutes of inactivity after which new threads in the channel are archived
+    pub default_auto_archive_duration: Option<u32>,
+    pub flags: Option<u64>,
+    /// The tags that can be applied to posts in a forum channel
+    #[serde(default)]
+    pub available_tags: Vec<ForumTag>,
+    /// The IDs of the tags applied to a post in a forum channel
+    #[serde(default)]
+    pub applied_tags: Vec<api::misc::Snowflake>,
+    /// The emoji shown as the reaction button on posts in a forum channel
+    pub default_reaction_emoji: Option<DefaultReaction>,
+    /// The slowmode applied to newly created posts in a forum channel
+    pub default_thread_rate_limit_per_user: Option<u16>,
+    /// How posts in a forum channel are sorted by default
+    pub default_sort_order: Option<SortOrderType>,
+    /// The first message in a forum post, which is only sent when the post is created
+    pub message: Option<Box<Message>>,
+}
+
+/// A [tag](https://discord.com/developers/docs/resources/channel#forum-tag-object) that can be
+/// applied to posts in a forum channel. Each tag can have either a custom or unicode emoji.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ForumTag {
+    /// The ID of the tag, which is `None` for tags that are being created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<api::misc::Snowflake>,
+    pub name: String,
+    /// Moderated tags can only be applied by members with `MANAGE_THREADS`
+    pub moderated: bool,
+    pub emoji_id: Option<api::misc::Snowflake>,
+    pub emoji_name: Option<String>,
+}
+
+/// The [emoji](https://discord.com/developers/docs/resources/channel#default-reaction-object) used
+/// for the reaction button on forum posts, which is either a custom or unicode emoji.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DefaultReaction {
+    pub emoji_id: Option<api::misc::Snowflake>,
+    pub emoji_name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum SortOrderType {
+    LatestActivity = 0,
+    CreationDate = 1,
+}
+
+/// Information about a [thread](https://discord.com/developers/docs/resources/channel#thread-metadata-object)
+/// that other channels don't have.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThreadMetadata {
+    pub archived: bool,
+    /// The number of minutes of inactivity after which the thread is archived
+    pub auto_archive_duration: u32,
+    /// ISO8601 timestamp of when the thread was last archived or unarchived
+    pub archive_timestamp: String,
+    /// Locked threads can only be unarchived by members with `MANAGE_THREADS`
+    pub locked: bool,
+    /// Whether non-moderators can add other non-moderators to a private thread
+    pub invitable: Option<bool>,
+    /// ISO8601 timestamp of when the thread was created, for threads created after 2022-01-09
+    pub create_timestamp: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum PermissionOverwriteType {
+    Role = 0,
+    Member = 1,
+}
+
+/// Explicitly [allows or denies](https://discord.com/developers/docs/resources/channel#overwrite-object)
+/// permissions to a role or member within a channel.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PermissionOverwrite {
+    /// The ID of the role or user that the overwrite applies to
+    pub id: api::misc::Snowflake,
+    #[serde(rename = "type")]
+    pub overwrite_type: PermissionOverwriteType,
+    pub allow: api::permissions::Permissions,
+    pub deny: api::permissions::Permissions,
+}
+
+/// The settings of a guild channel, used when
+/// [creating](https://discord.com/developers/docs/resources/guild#create-guild-channel) or
+/// [modifying](https://discord.com/developers/docs/resources/channel#modify-channel) a channel.
+/// Only the settings that have been set are sent to Discord.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ChannelBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    channel_type: Option<ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<Option<api::misc::Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<Option<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_limit: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    available_tags: Option<Vec<ForumTag>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_reaction_emoji: Option<DefaultReaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_thread_rate_limit_per_user: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_sort_order: Option<SortOrderType>,
+}
+
+impl ChannelBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(&mut self, name: String) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// The type of channel to create. Defaults to a text channel.
+    pub fn channel_type(&mut self, channel_type: ChannelType) -> &mut Self {
+        self.channel_type = Some(channel_type);
+        self
+    }
+
+    /// Sets the channel's topic, or clears it when `topic` is `None`.
+    pub fn topic(&mut self, topic: Option<String>) -> &mut Self {
+        self.topic = Some(topic);
+        self
+    }
+
+    /// The category that the channel is placed in, or `None` to move it out of its category.
+    pub fn parent(&mut self, parent_id: Option<api::misc::Snowflake>) -> &mut Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+
+    pub fn position(&mut self, position: i64) -> &mut Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn nsfw(&mut self, nsfw: bool) -> &mut Self {
+        self.nsfw = Some(nsfw);
+        self
+    }
+
+    /// The number of seconds users must wait between sending messages (slowmode), up to 21600,
+    /// or `None` to turn slowmode off.
+    pub fn rate_limit_per_user(&mut self, seconds: Option<u16>) -> &mut Self {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// The bitrate of a voice channel, in bits per second
+    pub fn bitrate(&mut self, bitrate: u32) -> &mut Self {
+        self.bitrate = Some(bitrate);
+        self
+    }
+
+    /// The most users that can be connected to a voice channel, where 0 means no limit
+    pub fn user_limit(&mut self, user_limit: u16) -> &mut Self {
+        self.user_limit = Some(user_limit);
+        self
+    }
+
+    /// Replaces all of the permission overwrites on the channel.
+    pub fn permission_overwrites(&mut self, overwrites: Vec<PermissionOverwrite>) -> &mut Self {
+        self.permission_overwrites = Some(overwrites);
+        self
+    }
+
+    /// Replaces the tags that can be applied to posts in a forum channel, up to 20. Existing
+    /// tags must be included with their ID to be kept.
+    pub fn available_tags(&mut self, tags: Vec<ForumTag>) -> &mut Self {
+        self.available_tags = Some(tags);
+        self
+    }
+
+    pub fn default_reaction_emoji(&mut self, emoji: DefaultReaction) -> &mut Self {
+        self.default_reaction_emoji = Some(emoji);
+        self
+    }
+
+    /// The slowmode applied to newly created posts in a forum channel
+    pub fn default_thread_rate_limit_per_user(&mut self, seconds: u16) -> &mut Self {
+        self.default_thread_rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    pub fn default_sort_order(&mut self, sort_order: SortOrderType) -> &mut Self {
+        self.default_sort_order = Some(sort_order);
+        self
+    }
+}
+
+/// The body of a new forum post: the thread itself along with its first message.
+#[derive(Debug, Serialize)]
+struct ForumPostPayload<'a> {
+    #[serde(flatten)]
+    thread: &'a api::thread::StartThreadBuilder,
+    message: MessagePayload<'a>,
+}
+
+/// A new position for a channel when
+/// [reordering](https://discord.com/developers/docs/resources/guild#modify-guild-channel-positions)
+/// the channels of a guild.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChannelPosition {
+    pub id: api::misc::Snowflake,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i64>,
+    /// Syncs the permissions of the channel with its new category
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_permissions: Option<bool>,
+    /// Moves the channel into a new category
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<api::misc::Snowflake>,
 }
 
 #[derive(Debug, Deserialize_repr)]
@@ -74,8 +381,468 @@ pub struct Message {
     pub application: Option<api::misc::Application>,
     pub application_id: Option<api::misc::Snowflake>,
     pub flags: Option<i64>,
+    #[serde(default)]
+    pub embeds: Vec<api::message::MessageEmbed>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
     #[serde(rename(deserialize = "type"))]
     pub message_type: MessageType,
+    pub message_reference: Option<MessageReference>,
+    /// The message this one replied to. This is only present on replies, and is `None` within
+    /// a reply if the original message has since been deleted.
+    pub referenced_message: Option<Box<Message>>,
+}
+
+/// A file that has been [attached](https://discord.com/developers/docs/resources/channel#attachment-object)
+/// to a message.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Attachment {
+    pub id: api::misc::Snowflake,
+    pub filename: String,
+    pub description: Option<String>,
+    pub content_type: Option<String>,
+    pub size: u64,
+    pub url: String,
+    pub proxy_url: String,
+    pub height: Option<i64>,
+    pub width: Option<i64>,
+    pub ephemeral: Option<bool>,
+}
+
+/// The [reactions](https://discord.com/developers/docs/resources/channel#reaction-object) to a
+/// message using a single emoji.
+#[derive(Debug, Deserialize)]
+pub struct Reaction {
+    pub count: u64,
+    /// Whether the bot is one of the users that reacted
+    pub me: bool,
+    pub emoji: api::emoji::Emoji,
+}
+
+/// Sent through the gateway when a user adds or removes a reaction to a message. The `member` is
+/// only included when a reaction is added to a message within a guild.
+#[derive(Debug, Deserialize)]
+pub struct MessageReactionEvent {
+    pub user_id: api::misc::Snowflake,
+    pub channel_id: api::misc::Snowflake,
+    pub message_id: api::misc::Snowflake,
+    pub guild_id: Option<api::misc::Snowflake>,
+    pub member: Option<api::guild::GuildMember>,
+    pub emoji: api::emoji::Emoji,
+}
+
+/// [Points](https://discord.com/developers/docs/resources/channel#message-reference-object-message-reference-structure)
+/// to the message that another message is replying to, crossposted from, or was pinned.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReference {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<api::misc::Snowflake>,
+    /// When sending a reply, whether Discord should refuse to send the message if the message
+    /// being replied to no longer exists. Defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_if_not_exists: Option<bool>,
+}
+
+/// Used to [edit](https://discord.com/developers/docs/resources/channel#edit-message) a message
+/// that was previously sent by the bot. Only the fields that have been set will be changed.
+#[derive(Debug, Default, Serialize)]
+pub struct EditMessageBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embeds: Option<Vec<api::message::MessageEmbed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<i64>,
+    #[serde(skip)]
+    retained_attachments: Option<Vec<api::misc::Snowflake>>,
+    #[serde(skip)]
+    files: Vec<AttachmentFile>,
+}
+
+/// An entry in the `attachments` of an edited message: either an attachment the message already
+/// has that should be kept, or a new file uploaded in the `files[n]` form field.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum EditAttachment<'a> {
+    Existing { id: api::misc::Snowflake },
+    New(PartialAttachment<'a>),
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct EditMessagePayload<'a> {
+    #[serde(flatten)]
+    message: &'a EditMessageBuilder,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<Vec<EditAttachment<'a>>>,
+}
+
+impl EditMessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn content(&mut self, content: String) -> &mut Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Adds an embed to the edited message. The embeds of the message are replaced by the embeds
+    /// added here, with the same limits as when sending a message.
+    pub fn embed(
+        &mut self,
+        embed: api::message::MessageEmbed,
+    ) -> Result<&mut Self, api::message::EmbedLimitError> {
+        api::message::push_embed(self.embeds.get_or_insert_with(Vec::new), embed)?;
+        Ok(self)
+    }
+
+    /// Removes all of the embeds from the message.
+    pub fn remove_embeds(&mut self) -> &mut Self {
+        self.embeds = Some(Vec::new());
+        self
+    }
+
+    /// Replaces the [components](https://discord.com/developers/docs/interactions/message-components)
+    /// of the message with their raw JSON representation.
+    pub fn components(&mut self, components: Vec<serde_json::Value>) -> &mut Self {
+        self.components = Some(components);
+        self
+    }
+
+    /// Sets the flags on the message. Only `SUPPRESS_EMBEDS` (`1 << 2`) can be changed.
+    pub fn flags(&mut self, flags: i64) -> &mut Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Keeps only the listed attachments on the message, removing every other attachment. By
+    /// default all of the message's existing attachments are kept.
+    pub fn retain_attachments(&mut self, attachment_ids: Vec<api::misc::Snowflake>) -> &mut Self {
+        self.retained_attachments = Some(attachment_ids);
+        self
+    }
+
+    /// Uploads a new file to the message, alongside the attachments that are being kept.
+    pub fn attach(&mut self, file: AttachmentFile) -> &mut Self {
+        self.files.push(file);
+        self
+    }
+
+    /// The body sent to Discord for the edit. Any attachment left out of the list is removed, so
+    /// when new files are uploaded the `existing` attachments are explicitly listed to keep them,
+    /// unless only some of them are being retained.
+    pub(crate) fn payload<'a>(
+        &'a self,
+        existing: &[api::misc::Snowflake],
+    ) -> EditMessagePayload<'a> {
+        if self.retained_attachments.is_none() && self.files.is_empty() {
+            return EditMessagePayload { message: self, attachments: None };
+        }
+
+        let retained = self.retained_attachments.as_deref().unwrap_or(existing);
+        let attachments = retained
+            .iter()
+            .map(|&id| EditAttachment::Existing { id })
+            .chain(self.files.iter().enumerate().map(|(id, file)| {
+                EditAttachment::New(PartialAttachment {
+                    id,
+                    filename: file.filename(),
+                    description: &file.description,
+                })
+            }))
+            .collect();
+        EditMessagePayload {
+            message: self,
+            attachments: Some(attachments),
+        }
+    }
+
+    pub(crate) fn file_parts(&self) -> Vec<(String, Vec<u8>)> {
+        self.files.iter().map(AttachmentFile::form_part).collect()
+    }
+
+    /// Whether the message's existing attachments need to be listed in the
+    /// [payload](EditMessageBuilder::payload) to keep them.
+    pub(crate) fn keeps_existing_attachments(&self) -> bool {
+        self.retained_attachments.is_none() && !self.files.is_empty()
+    }
+}
+
+/// Selects which messages are returned when
+/// [reading](https://discord.com/developers/docs/resources/channel#get-channel-messages) the
+/// history of a channel. Only one of `around`, `before` and `after` can be used at a time, so
+/// setting one of them clears the others.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GetMessagesBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    around: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u8>,
+}
+
+impl GetMessagesBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn around(&mut self, message_id: api::misc::Snowflake) -> &mut Self {
+        self.around = Some(message_id);
+        self.before = None;
+        self.after = None;
+        self
+    }
+
+    pub fn before(&mut self, message_id: api::misc::Snowflake) -> &mut Self {
+        self.around = None;
+        self.before = Some(message_id);
+        self.after = None;
+        self
+    }
+
+    pub fn after(&mut self, message_id: api::misc::Snowflake) -> &mut Self {
+        self.around = None;
+        self.before = None;
+        self.after = Some(message_id);
+        self
+    }
+
+    /// The number of messages to return, between 1 and 100. Defaults to 50.
+    pub fn limit(&mut self, limit: u8) -> &mut Self {
+        self.limit = Some(limit.clamp(1, MESSAGE_PAGE_LIMIT));
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ReactionUsersQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<api::misc::Snowflake>,
+    limit: u8,
+}
+
+/// The most users that can be fetched for a reaction in a single request
+const REACTION_USERS_PAGE_LIMIT: u8 = 100;
+
+/// The most messages that can be fetched from a channel in a single request
+const MESSAGE_PAGE_LIMIT: u8 = 100;
+
+/// How often a [`TypingGuard`] re-triggers the typing indicator, which lasts for 10 seconds
+const TYPING_INTERVAL: Duration = Duration::from_secs(8);
+
+/// Keeps the bot shown as typing in a channel until the guard is dropped. Created with
+/// [`Channel::start_typing`].
+#[derive(Debug)]
+pub struct TypingGuard(tokio::task::JoinHandle<()>);
+
+impl Drop for TypingGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+async fn trigger_typing_in(config: &api::config::BotConfig, channel_id: &str) {
+    api::base::post_empty(config, format!("channels/{}/typing", channel_id)).await
+}
+
+/// Messages older than this can't be bulk deleted
+const BULK_DELETE_MAX_AGE_MILLIS: u64 = 14 * 24 * 60 * 60 * 1000;
+
+/// Returned when a set of messages can't be [bulk deleted](Channel::bulk_delete).
+#[derive(Debug, PartialEq, Eq)]
+pub enum BulkDeleteError {
+    /// Between 2 and 100 messages must be deleted at once
+    InvalidMessageCount(usize),
+    /// The message is older than 2 weeks
+    MessageTooOld(api::misc::Snowflake),
+}
+
+impl fmt::Display for BulkDeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMessageCount(count) => write!(
+                f,
+                "Bulk deletes require between 2 and 100 messages, but {} were given",
+                count
+            ),
+            Self::MessageTooOld(id) => write!(
+                f,
+                "Message {} is more than 2 weeks old and can't be bulk deleted",
+                id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BulkDeleteError {}
+
+impl Message {
+    /// A reference that can be used to reply to this message.
+    pub fn reference(&self) -> MessageReference {
+        MessageReference {
+            message_id: Some(self.id),
+            channel_id: Some(api::misc::Snowflake::from_string(self.channel_id.clone())),
+            guild_id: self.guild_id.clone().map(api::misc::Snowflake::from_string),
+            fail_if_not_exists: None,
+        }
+    }
+
+    /// Sends `message` to this message's channel as a reply to this message. Any reference that
+    /// was already set on `message` is replaced, apart from its `fail_if_not_exists`, which can be
+    /// customized that way.
+    pub async fn reply(
+        &self,
+        config: &api::config::BotConfig,
+        mut message: SendMessageBuilder,
+    ) -> Message {
+        let mut reference = self.reference();
+        reference.fail_if_not_exists = message
+            .message_reference
+            .take()
+            .and_then(|existing| existing.fail_if_not_exists);
+        message.reference(reference);
+        message.post_to(config, &self.channel_id).await
+    }
+
+    /// Edits a message that was sent by the bot, returning the updated message.
+    pub async fn edit(
+        &self,
+        config: &api::config::BotConfig,
+        message: &EditMessageBuilder,
+    ) -> Message {
+        let endpoint = format!("channels/{}/messages/{}", self.channel_id, self.id);
+        let existing: Vec<_> = self.attachments.iter().map(|attachment| attachment.id).collect();
+        let payload = message.payload(&existing);
+        if message.files.is_empty() {
+            return api::base::patch(config, endpoint, payload, None).await;
+        }
+        api::base::patch_multipart(config, endpoint, payload, message.file_parts()).await
+    }
+
+    fn reactions_endpoint(&self, emoji: &api::emoji::ReactionEmoji) -> String {
+        format!(
+            "channels/{}/messages/{}/reactions/{}",
+            self.channel_id,
+            self.id,
+            emoji.url_encoded()
+        )
+    }
+
+    /// Reacts to this message as the bot.
+    pub async fn react(&self, config: &api::config::BotConfig, emoji: &api::emoji::ReactionEmoji) {
+        let endpoint = format!("{}/@me", self.reactions_endpoint(emoji));
+        api::base::put_empty(config, endpoint, None).await
+    }
+
+    /// Removes the bot's own reaction to this message.
+    pub async fn delete_own_reaction(
+        &self,
+        config: &api::config::BotConfig,
+        emoji: &api::emoji::ReactionEmoji,
+    ) {
+        api::base::delete(config, format!("{}/@me", self.reactions_endpoint(emoji)), None).await
+    }
+
+    /// Removes another user's reaction to this message.
+    pub async fn delete_user_reaction(
+        &self,
+        config: &api::config::BotConfig,
+        emoji: &api::emoji::ReactionEmoji,
+        user_id: api::misc::Snowflake,
+    ) {
+        let endpoint = format!("{}/{}", self.reactions_endpoint(emoji), user_id);
+        api::base::delete(config, endpoint, None).await
+    }
+
+    /// Fetches a page of up to `limit` (at most 100) users that reacted with `emoji`, starting
+    /// with the first user whose ID is greater than `after`.
+    pub async fn reaction_users(
+        &self,
+        config: &api::config::BotConfig,
+        emoji: &api::emoji::ReactionEmoji,
+        after: Option<api::misc::Snowflake>,
+        limit: u8,
+    ) -> Vec<api::user::User> {
+        let query = ReactionUsersQuery {
+            after,
+            limit: limit.clamp(1, REACTION_USERS_PAGE_LIMIT),
+        };
+        api::base::get_with_query(config, self.reactions_endpoint(emoji), query).await
+    }
+
+    /// Streams every user that reacted to this message with `emoji`, requesting pages of users
+    /// as the stream is consumed.
+    pub fn all_reaction_users<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+        emoji: &'a api::emoji::ReactionEmoji,
+    ) -> impl Stream<Item = api::user::User> + 'a {
+        api::base::paginate(
+            None,
+            move |after| self.reaction_users(config, emoji, after, REACTION_USERS_PAGE_LIMIT),
+            |_, page: &Vec<api::user::User>| match page.last() {
+                Some(last) if page.len() == REACTION_USERS_PAGE_LIMIT as usize => {
+                    Some(Some(last.id))
+                }
+                _ => None,
+            },
+        )
+    }
+
+    /// Removes every reaction from this message.
+    pub async fn delete_all_reactions(&self, config: &api::config::BotConfig) {
+        let endpoint = format!("channels/{}/messages/{}/reactions", self.channel_id, self.id);
+        api::base::delete(config, endpoint, None).await
+    }
+
+    /// Removes every reaction to this message that used `emoji`.
+    pub async fn delete_all_reactions_for_emoji(
+        &self,
+        config: &api::config::BotConfig,
+        emoji: &api::emoji::ReactionEmoji,
+    ) {
+        api::base::delete(config, self.reactions_endpoint(emoji), None).await
+    }
+
+    /// Starts a public thread from this message, returning the new thread.
+    pub async fn start_thread(
+        &self,
+        config: &api::config::BotConfig,
+        thread: &api::thread::StartThreadBuilder,
+        reason: Option<String>,
+    ) -> Channel {
+        let endpoint = format!("channels/{}/messages/{}/threads", self.channel_id, self.id);
+        api::base::post(config, endpoint, thread, reason).await
+    }
+
+    /// Pins this message to its channel.
+    pub async fn pin(&self, config: &api::config::BotConfig, reason: Option<String>) {
+        let endpoint = format!("channels/{}/pins/{}", self.channel_id, self.id);
+        api::base::put_empty(config, endpoint, reason).await
+    }
+
+    /// Unpins this message from its channel.
+    pub async fn unpin(&self, config: &api::config::BotConfig, reason: Option<String>) {
+        let endpoint = format!("channels/{}/pins/{}", self.channel_id, self.id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    /// Deletes this message. The `reason` is recorded in the guild's audit log.
+    pub async fn delete(&self, config: &api::config::BotConfig, reason: Option<String>) {
+        let endpoint = format!("channels/{}/messages/{}", self.channel_id, self.id);
+        api::base::delete(config, endpoint, reason).await
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -85,10 +852,115 @@ pub enum SendMessageContent {
     StickersMessage(Vec<String>),
 }
 
+/// A file that will be uploaded alongside a message. Once attached to a message, embeds within
+/// that message can reference the file through [`AttachmentFile::attachment_url`].
+#[derive(Clone, Debug)]
+pub struct AttachmentFile {
+    filename: String,
+    description: Option<String>,
+    spoiler: bool,
+    data: Vec<u8>,
+}
+
+impl AttachmentFile {
+    pub fn from_bytes(filename: String, data: Vec<u8>) -> Self {
+        Self {
+            filename,
+            data,
+            description: None,
+            spoiler: false,
+        }
+    }
+
+    /// Reads the file at `path` into memory, naming the attachment after the file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid file name"))?;
+        Ok(Self::from_bytes(filename.to_string(), fs::read(path)?))
+    }
+
+    /// Alt text for the attachment.
+    pub fn description(&mut self, description: String) -> &mut Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Spoilered attachments are blurred in the client until they're clicked on.
+    pub fn spoiler(&mut self, spoiler: bool) -> &mut Self {
+        self.spoiler = spoiler;
+        self
+    }
+
+    /// The name the file will be uploaded with. Discord marks attachments as spoilers based on
+    /// their name, so this includes the `SPOILER_` prefix when the attachment is a spoiler.
+    pub fn filename(&self) -> String {
+        if self.spoiler {
+            format!("SPOILER_{}", self.filename)
+        } else {
+            self.filename.clone()
+        }
+    }
+
+    /// The URL used to reference this file from an embed in the same message, such as an embed
+    /// image.
+    pub fn attachment_url(&self) -> String {
+        format!("attachment://{}", self.filename())
+    }
+
+    /// The name and contents of the file, as they're uploaded in a multipart form.
+    pub(crate) fn form_part(&self) -> (String, Vec<u8>) {
+        (self.filename(), self.data.clone())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PartialAttachment<'a> {
+    id: usize,
+    filename: String,
+    description: &'a Option<String>,
+}
+
+/// Files aren't sent as part of the JSON payload, only the metadata describing each of them. The
+/// ID of each attachment is the index of the `files[n]` form field that contains its data.
+pub(crate) fn serialize_attachments<S: Serializer>(
+    files: &[AttachmentFile],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(files.iter().enumerate().map(|(id, file)| PartialAttachment {
+        id,
+        filename: file.filename(),
+        description: &file.description,
+    }))
+}
+
 #[derive(Debug, Serialize)]
 pub struct SendMessageBuilder {
     tts: bool,
     content: SendMessageContent,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<api::message::MessageEmbed>,
+    #[serde(
+        rename(serialize = "attachments"),
+        serialize_with = "serialize_attachments",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    files: Vec<AttachmentFile>,
+    #[serde(skip)]
+    allowed_mentions: Option<api::message::AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_reference: Option<MessageReference>,
+}
+
+/// The body sent to Discord for a message, which fills in the config's allowed mentions when the
+/// message doesn't set its own.
+#[derive(Debug, Serialize)]
+struct MessagePayload<'a> {
+    #[serde(flatten)]
+    message: &'a SendMessageBuilder,
+    allowed_mentions: &'a api::message::AllowedMentions,
 }
 
 impl SendMessageBuilder {
@@ -96,16 +968,71 @@ impl SendMessageBuilder {
         Self {
             content,
             tts: false,
+            embeds: Vec::new(),
+            files: Vec::new(),
+            allowed_mentions: None,
+            message_reference: None,
         }
     }
 
     pub fn tts(&mut self, tts: bool) -> &mut Self {
         self.tts = tts;
-        return self;
+        self
+    }
+
+    /// Adds an embed to this message. A message can contain up to 10 embeds, which combined
+    /// can't contain more than 6000 characters.
+    pub fn embed(
+        &mut self,
+        embed: api::message::MessageEmbed,
+    ) -> Result<&mut Self, api::message::EmbedLimitError> {
+        api::message::push_embed(&mut self.embeds, embed)?;
+        Ok(self)
+    }
+
+    /// Overrides the config's allowed mentions for this message.
+    pub fn allowed_mentions(&mut self, mentions: api::message::AllowedMentions) -> &mut Self {
+        self.allowed_mentions = Some(mentions);
+        self
+    }
+
+    /// Uploads a file alongside this message.
+    pub fn attach(&mut self, file: AttachmentFile) -> &mut Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Replies to another message, showing it above this one in the client.
+    pub fn reference(&mut self, reference: MessageReference) -> &mut Self {
+        self.message_reference = Some(reference);
+        self
+    }
+
+    pub async fn send_to(&self, config: &api::config::BotConfig, channel: &Channel) -> Message {
+        channel.create_message(config, self).await
+    }
+
+    fn payload<'a>(&'a self, config: &'a api::config::BotConfig) -> MessagePayload<'a> {
+        MessagePayload {
+            message: self,
+            allowed_mentions: self
+                .allowed_mentions
+                .as_ref()
+                .unwrap_or(&config.allowed_mentions),
+        }
     }
 
-    pub fn send_to(&self, config: &api::config::BotConfig, channel: &Channel) -> Message {
-        api::base::post(config, format!("channels/{}/messages", channel.id), self)
+    fn file_parts(&self) -> Vec<(String, Vec<u8>)> {
+        self.files.iter().map(AttachmentFile::form_part).collect()
+    }
+
+    async fn post_to(&self, config: &api::config::BotConfig, channel_id: &str) -> Message {
+        let endpoint = format!("channels/{}/messages", channel_id);
+        let payload = self.payload(config);
+        if self.files.is_empty() {
+            return api::base::post(config, endpoint, payload, None).await;
+        }
+        api::base::post_multipart(config, endpoint, payload, self.file_parts(), None).await
     }
 
     pub fn text(message: String) -> Self {
@@ -114,15 +1041,239 @@ impl SendMessageBuilder {
 }
 
 impl Channel {
-    pub fn get(config: &api::config::BotConfig, channel_id: api::misc::Snowflake) -> Self {
-        api::base::get(config, format!("channels/{}", channel_id))
+    pub async fn get(config: &api::config::BotConfig, channel_id: api::misc::Snowflake) -> Self {
+        api::base::get(config, format!("channels/{}", channel_id)).await
     }
 
-    pub fn create_message(
+    pub async fn create_message(
         &self,
         config: &api::config::BotConfig,
         message: &SendMessageBuilder,
     ) -> Message {
-        api::base::post(config, format!("channels/{}/messages", self.id), message)
+        message.post_to(config, &self.id).await
+    }
+
+    /// Changes the settings of the channel, returning the updated channel.
+    pub async fn modify(
+        &self,
+        config: &api::config::BotConfig,
+        changes: &ChannelBuilder,
+        reason: Option<String>,
+    ) -> Channel {
+        api::base::patch(config, format!("channels/{}", self.id), changes, reason).await
+    }
+
+    /// Deletes the channel, or closes it if it's a direct message. Returns the deleted channel.
+    pub async fn delete(&self, config: &api::config::BotConfig, reason: Option<String>) -> Channel {
+        api::base::delete(config, format!("channels/{}", self.id), reason).await
+    }
+
+    /// Starts a thread in the channel that isn't attached to a message, returning the new thread.
+    pub async fn start_thread(
+        &self,
+        config: &api::config::BotConfig,
+        thread: &api::thread::StartThreadBuilder,
+        reason: Option<String>,
+    ) -> Channel {
+        api::base::post(config, format!("channels/{}/threads", self.id), thread, reason).await
+    }
+
+    /// Creates a post in this forum channel, which is a thread along with its first message. The
+    /// returned thread includes the first message.
+    pub async fn create_forum_post(
+        &self,
+        config: &api::config::BotConfig,
+        thread: &api::thread::StartThreadBuilder,
+        message: &SendMessageBuilder,
+        reason: Option<String>,
+    ) -> Channel {
+        let endpoint = format!("channels/{}/threads", self.id);
+        let payload = ForumPostPayload {
+            thread,
+            message: message.payload(config),
+        };
+        if message.files.is_empty() {
+            return api::base::post(config, endpoint, payload, reason).await;
+        }
+        api::base::post_multipart(config, endpoint, payload, message.file_parts(), reason).await
+    }
+
+    /// Adds the bot to this thread.
+    pub async fn join_thread(&self, config: &api::config::BotConfig) {
+        let endpoint = format!("channels/{}/thread-members/@me", self.id);
+        api::base::put_empty(config, endpoint, None).await
+    }
+
+    /// Removes the bot from this thread.
+    pub async fn leave_thread(&self, config: &api::config::BotConfig) {
+        let endpoint = format!("channels/{}/thread-members/@me", self.id);
+        api::base::delete(config, endpoint, None).await
+    }
+
+    /// Adds another user to this thread.
+    pub async fn add_thread_member(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+    ) {
+        let endpoint = format!("channels/{}/thread-members/{}", self.id, user_id);
+        api::base::put_empty(config, endpoint, None).await
+    }
+
+    /// Removes another user from this thread.
+    pub async fn remove_thread_member(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+    ) {
+        let endpoint = format!("channels/{}/thread-members/{}", self.id, user_id);
+        api::base::delete(config, endpoint, None).await
+    }
+
+    /// Fetches a page of archived threads in this channel, newest first, starting with the
+    /// threads that come after `before`. See
+    /// [`ArchivedThreads::cursor`](api::thread::ArchivedThreads::cursor) for the value of
+    /// `before` to use for each list.
+    pub async fn archived_threads(
+        &self,
+        config: &api::config::BotConfig,
+        list: api::thread::ArchivedThreads,
+        before: Option<String>,
+        limit: Option<u32>,
+    ) -> api::thread::ThreadList {
+        let query = api::thread::ArchivedThreadsQuery { before, limit };
+        api::base::get_with_query(config, list.endpoint(&self.id), query).await
+    }
+
+    /// Streams every archived thread in one of this channel's lists of archived threads,
+    /// requesting pages of threads as the stream is consumed.
+    pub fn all_archived_threads<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+        list: api::thread::ArchivedThreads,
+    ) -> impl Stream<Item = Channel> + 'a {
+        api::base::paginate(
+            None,
+            move |before| self.archived_threads(config, list, before, None),
+            move |_, page: &api::thread::ThreadList| match page.threads.last() {
+                Some(last) if page.has_more => list.cursor(last).map(Some),
+                _ => None,
+            },
+        )
+    }
+
+    /// Fetches every message pinned in the channel.
+    pub async fn pins(&self, config: &api::config::BotConfig) -> Vec<Message> {
+        api::base::get(config, format!("channels/{}/pins", self.id)).await
+    }
+
+    /// Lists the invites to the channel. This requires the `MANAGE_CHANNELS` permission.
+    pub async fn invites(&self, config: &api::config::BotConfig) -> Vec<api::invite::Invite> {
+        api::base::get(config, format!("channels/{}/invites", self.id)).await
+    }
+
+    /// Creates an invite to the channel. This requires the `CREATE_INSTANT_INVITE` permission.
+    pub async fn create_invite(
+        &self,
+        config: &api::config::BotConfig,
+        invite: &api::invite::CreateInviteBuilder,
+        reason: Option<String>,
+    ) -> api::invite::Invite {
+        api::base::post(config, format!("channels/{}/invites", self.id), invite, reason).await
+    }
+
+    /// Lists the webhooks in the channel. This requires the `MANAGE_WEBHOOKS` permission.
+    pub async fn webhooks(&self, config: &api::config::BotConfig) -> Vec<api::webhook::Webhook> {
+        api::base::get(config, format!("channels/{}/webhooks", self.id)).await
+    }
+
+    /// Creates an incoming webhook in the channel. The webhook's `name` must be set on `webhook`.
+    /// This requires the `MANAGE_WEBHOOKS` permission.
+    pub async fn create_webhook(
+        &self,
+        config: &api::config::BotConfig,
+        webhook: &api::webhook::WebhookBuilder,
+        reason: Option<String>,
+    ) -> api::webhook::Webhook {
+        api::base::post(config, format!("channels/{}/webhooks", self.id), webhook, reason).await
+    }
+
+    /// Shows the bot as typing in the channel for 10 seconds, or until it sends a message.
+    pub async fn trigger_typing(&self, config: &api::config::BotConfig) {
+        trigger_typing_in(config, &self.id).await
+    }
+
+    /// Shows the bot as typing in the channel until the returned guard is dropped, which is
+    /// useful while running a command that takes a while to respond. This must be called from
+    /// within a tokio runtime.
+    pub fn start_typing(&self, config: &api::config::BotConfig) -> TypingGuard {
+        let config = config.clone();
+        let channel_id = self.id.clone();
+        TypingGuard(tokio::spawn(async move {
+            loop {
+                trigger_typing_in(&config, &channel_id).await;
+                tokio::time::sleep(TYPING_INTERVAL).await;
+            }
+        }))
+    }
+
+    /// Fetches a single page of messages from the channel, newest first.
+    pub async fn messages(
+        &self,
+        config: &api::config::BotConfig,
+        query: &GetMessagesBuilder,
+    ) -> Vec<Message> {
+        let endpoint = format!("channels/{}/messages", self.id);
+        api::base::get_with_query(config, endpoint, query).await
+    }
+
+    /// Streams the entire history of the channel, from the newest message to the oldest, or
+    /// from just before `before` when it is provided. Pages are requested as the stream is
+    /// consumed, and requests wait out the channel's rate limit between pages.
+    pub fn history<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+        before: Option<api::misc::Snowflake>,
+    ) -> impl Stream<Item = Message> + 'a {
+        let fetch_page = move |before: Option<api::misc::Snowflake>| {
+            let mut query = GetMessagesBuilder::new();
+            query.limit(MESSAGE_PAGE_LIMIT);
+            if let Some(before) = before {
+                query.before(before);
+            }
+            async move { self.messages(config, &query).await }
+        };
+        api::base::paginate(before, fetch_page, |_, page: &Vec<Message>| match page.last() {
+            Some(oldest) if page.len() == MESSAGE_PAGE_LIMIT as usize => Some(Some(oldest.id)),
+            _ => None,
+        })
+    }
+
+    /// Deletes between 2 and 100 messages at once. Discord doesn't allow messages that are more
+    /// than 2 weeks old to be bulk deleted, so those are rejected before making the request.
+    pub async fn bulk_delete(
+        &self,
+        config: &api::config::BotConfig,
+        message_ids: Vec<api::misc::Snowflake>,
+        reason: Option<String>,
+    ) -> Result<(), BulkDeleteError> {
+        if !(2..=100).contains(&message_ids.len()) {
+            return Err(BulkDeleteError::InvalidMessageCount(message_ids.len()));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is before the Unix epoch")
+            .as_millis() as u64;
+        let oldest_allowed = now.saturating_sub(BULK_DELETE_MAX_AGE_MILLIS);
+        if let Some(too_old) = message_ids.iter().find(|id| id.timestamp() < oldest_allowed) {
+            return Err(BulkDeleteError::MessageTooOld(*too_old));
+        }
+
+        let mut payload = HashMap::new();
+        payload.insert("messages", message_ids);
+        let endpoint = format!("channels/{}/messages/bulk-delete", self.id);
+        api::base::post::<(), _>(config, endpoint, payload, reason).await;
+        Ok(())
     }
 }
@@ -1,13 +1,18 @@
+use crate::api;
+
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io;
 use std::path::PathBuf;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BotConfig {
     pub token: String,
     pub application_id: String,
     pub intents: u32,
+    /// The mentions allowed in messages that don't specify their own
+    #[serde(default)]
+    pub allowed_mentions: api::message::AllowedMentions,
 }
 
 pub fn load_config(filename: PathBuf) -> io::Result<BotConfig> {
@@ -0,0 +1,125 @@
+use crate::api;
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// An [emoji](https://discord.com/developers/docs/resources/emoji#emoji-object), which is either a
+/// standard unicode emoji, or a custom emoji uploaded to a guild.
+#[derive(Debug, Deserialize)]
+pub struct Emoji {
+    /// The ID of a custom emoji. Unicode emojis don't have an ID.
+    pub id: Option<api::misc::Snowflake>,
+    /// The name of a custom emoji, or the emoji itself for unicode emojis. This can be `None`
+    /// for custom emojis in reaction events when the emoji has been deleted.
+    pub name: Option<String>,
+    #[serde(default)]
+    pub roles: Vec<api::misc::Snowflake>,
+    pub user: Option<api::user::User>,
+    pub require_colons: Option<bool>,
+    pub managed: Option<bool>,
+    pub animated: Option<bool>,
+    pub available: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct CreateEmoji {
+    pub name: String,
+    /// The emoji's image as a data URI
+    pub image: String,
+    pub roles: Vec<api::misc::Snowflake>,
+}
+
+/// Used to [modify](https://discord.com/developers/docs/resources/emoji#modify-guild-emoji) a
+/// custom emoji. Only the settings that have been set are sent to Discord.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ModifyEmojiBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<api::misc::Snowflake>>,
+}
+
+impl ModifyEmojiBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(&mut self, name: String) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// The roles that are allowed to use the emoji, where an empty list allows everyone.
+    pub fn roles(&mut self, role_ids: Vec<api::misc::Snowflake>) -> &mut Self {
+        self.roles = Some(role_ids);
+        self
+    }
+}
+
+/// Sent through the gateway when a guild's custom emojis change, with the full set of emojis.
+#[derive(Debug, Deserialize)]
+pub struct GuildEmojisUpdate {
+    pub guild_id: api::misc::Snowflake,
+    pub emojis: Vec<Emoji>,
+}
+
+/// An emoji that can be used to react to a message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReactionEmoji {
+    Unicode(String),
+    Custom { name: String, id: api::misc::Snowflake },
+}
+
+impl ReactionEmoji {
+    /// Formats the emoji for use in a URL path, which is the emoji itself for unicode emojis and
+    /// `name:id` for custom emojis.
+    pub fn url_encoded(&self) -> String {
+        let emoji = match self {
+            Self::Unicode(emoji) => emoji.clone(),
+            Self::Custom { name, id } => format!("{}:{}", name, id),
+        };
+        utf8_percent_encode(&emoji, NON_ALPHANUMERIC).to_string()
+    }
+}
+
+impl fmt::Display for ReactionEmoji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unicode(emoji) => write!(f, "{}", emoji),
+            Self::Custom { name, id } => write!(f, "<:{}:{}>", name, id),
+        }
+    }
+}
+
+/// Parses an emoji from the way it's written in a message (`<:name:id>` or `<a:name:id>`) or in
+/// a URL (`name:id`). Anything else is treated as a unicode emoji.
+impl FromStr for ReactionEmoji {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let custom = s
+            .strip_prefix("<a:")
+            .or_else(|| s.strip_prefix("<:"))
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(s);
+        match custom.rsplit_once(':') {
+            Some((name, id)) => Ok(Self::Custom {
+                name: name.to_string(),
+                id: id.parse::<u64>()?.into(),
+            }),
+            None => Ok(Self::Unicode(s.to_string())),
+        }
+    }
+}
+
+impl From<&Emoji> for ReactionEmoji {
+    fn from(emoji: &Emoji) -> Self {
+        let name = emoji.name.clone().unwrap_or_default();
+        match emoji.id {
+            Some(id) => Self::Custom { name, id },
+            None => Self::Unicode(name),
+        }
+    }
+}
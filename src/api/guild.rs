@@ -1,9 +1,16 @@
 use crate::api;
-use serde::Deserialize;
+use futures::stream::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+/// A guild that the bot can't access due to an outage, in which case Discord only sends its ID.
 #[derive(Debug, Deserialize)]
 pub struct UnavailableGuild {
     pub id: api::misc::Snowflake,
+    /// This is `false` when the bot has been removed from the guild, rather than the guild
+    /// becoming unavailable
+    #[serde(default)]
+    pub unavailable: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -13,28 +20,384 @@ pub struct Role {
     pub color: i64,
     pub hoist: bool,
     pub position: i64,
-    pub permissions: i64,
-    pub permissions_new: String,
+    pub permissions: api::permissions::Permissions,
     pub managed: bool,
     pub mentionable: bool,
+    /// The hash of the role's icon
+    pub icon: Option<String>,
+    pub unicode_emoji: Option<String>,
+}
+
+/// The settings of a role, used when
+/// [creating](https://discord.com/developers/docs/resources/guild#create-guild-role) or
+/// [modifying](https://discord.com/developers/docs/resources/guild#modify-guild-role) a role.
+/// Only the settings that have been set are sent to Discord.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RoleBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<api::permissions::Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_emoji: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+impl RoleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(&mut self, name: String) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn permissions(&mut self, permissions: api::permissions::Permissions) -> &mut Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// The RGB color of the role, such as `0xE67E22`
+    pub fn color(&mut self, color: i64) -> &mut Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Whether members with the role are shown separately in the member list
+    pub fn hoist(&mut self, hoist: bool) -> &mut Self {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// The role's icon, as a data URI created with [`api::misc::image_data_uri`]. The guild
+    /// needs the `ROLE_ICONS` feature.
+    pub fn icon(&mut self, icon: String) -> &mut Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// A unicode emoji used as the role's icon. The guild needs the `ROLE_ICONS` feature.
+    pub fn unicode_emoji(&mut self, emoji: String) -> &mut Self {
+        self.unicode_emoji = Some(emoji);
+        self
+    }
+
+    /// Whether everyone can mention the role
+    pub fn mentionable(&mut self, mentionable: bool) -> &mut Self {
+        self.mentionable = Some(mentionable);
+        self
+    }
+}
+
+/// A new position for a role when
+/// [reordering](https://discord.com/developers/docs/resources/guild#modify-guild-role-positions)
+/// the roles of a guild.
+#[derive(Clone, Debug, Serialize)]
+pub struct RolePosition {
+    pub id: api::misc::Snowflake,
+    pub position: i64,
+}
+
+/// Used to [modify](https://discord.com/developers/docs/resources/guild#modify-guild-member) a
+/// member of a guild. Only the settings that have been set are sent to Discord.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ModifyMemberBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<api::misc::Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deaf: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Option<api::misc::Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    communication_disabled_until: Option<Option<String>>,
+}
+
+impl ModifyMemberBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the member's nickname, or resets it to their username when `nick` is `None`.
+    pub fn nick(&mut self, nick: Option<String>) -> &mut Self {
+        self.nick = Some(nick);
+        self
+    }
+
+    /// Replaces all of the member's roles.
+    pub fn roles(&mut self, role_ids: Vec<api::misc::Snowflake>) -> &mut Self {
+        self.roles = Some(role_ids);
+        self
+    }
+
+    /// Whether the member is muted in voice channels
+    pub fn mute(&mut self, mute: bool) -> &mut Self {
+        self.mute = Some(mute);
+        self
+    }
+
+    /// Whether the member is deafened in voice channels
+    pub fn deaf(&mut self, deaf: bool) -> &mut Self {
+        self.deaf = Some(deaf);
+        self
+    }
+
+    /// Moves the member to another voice channel. The member must already be connected to voice.
+    pub fn move_to_channel(&mut self, channel_id: api::misc::Snowflake) -> &mut Self {
+        self.channel_id = Some(Some(channel_id));
+        self
+    }
+
+    /// Disconnects the member from voice.
+    pub fn disconnect(&mut self) -> &mut Self {
+        self.channel_id = Some(None);
+        self
+    }
+
+    /// Times the member out until `until`, which can be at most 28 days in the future, or
+    /// removes their timeout when `until` is `None`.
+    pub fn communication_disabled_until(
+        &mut self,
+        until: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> &mut Self {
+        self.communication_disabled_until = Some(until.map(|until| until.to_rfc3339()));
+        self
+    }
+}
+
+/// Sent through the gateway when a role is created or updated.
+#[derive(Debug, Deserialize)]
+pub struct GuildRoleEvent {
+    pub guild_id: api::misc::Snowflake,
+    pub role: Role,
+}
+
+/// Sent through the gateway when a role is deleted.
+#[derive(Debug, Deserialize)]
+pub struct GuildRoleDelete {
+    pub guild_id: api::misc::Snowflake,
+    pub role_id: api::misc::Snowflake,
+}
+
+/// A [feature](https://discord.com/developers/docs/resources/guild#guild-object-guild-features)
+/// that has been enabled for a guild. Discord regularly adds new features, so features this
+/// crate doesn't know about yet are kept as `Unknown`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum GuildFeature {
+    AnimatedBanner,
+    AnimatedIcon,
+    ApplicationCommandPermissionsV2,
+    AutoModeration,
+    Banner,
+    Community,
+    CreatorMonetizableProvisional,
+    CreatorStorePage,
+    DeveloperSupportServer,
+    Discoverable,
+    Featurable,
+    InvitesDisabled,
+    InviteSplash,
+    MemberVerificationGateEnabled,
+    MoreStickers,
+    News,
+    Partnered,
+    PreviewEnabled,
+    RaidAlertsDisabled,
+    RoleIcons,
+    RoleSubscriptionsAvailableForPurchase,
+    RoleSubscriptionsEnabled,
+    TicketedEventsEnabled,
+    VanityUrl,
+    Verified,
+    VipRegions,
+    WelcomeScreenEnabled,
+    Unknown(String),
+}
+
+impl From<String> for GuildFeature {
+    fn from(feature: String) -> Self {
+        match feature.as_str() {
+            "ANIMATED_BANNER" => Self::AnimatedBanner,
+            "ANIMATED_ICON" => Self::AnimatedIcon,
+            "APPLICATION_COMMAND_PERMISSIONS_V2" => Self::ApplicationCommandPermissionsV2,
+            "AUTO_MODERATION" => Self::AutoModeration,
+            "BANNER" => Self::Banner,
+            "COMMUNITY" => Self::Community,
+            "CREATOR_MONETIZABLE_PROVISIONAL" => Self::CreatorMonetizableProvisional,
+            "CREATOR_STORE_PAGE" => Self::CreatorStorePage,
+            "DEVELOPER_SUPPORT_SERVER" => Self::DeveloperSupportServer,
+            "DISCOVERABLE" => Self::Discoverable,
+            "FEATURABLE" => Self::Featurable,
+            "INVITES_DISABLED" => Self::InvitesDisabled,
+            "INVITE_SPLASH" => Self::InviteSplash,
+            "MEMBER_VERIFICATION_GATE_ENABLED" => Self::MemberVerificationGateEnabled,
+            "MORE_STICKERS" => Self::MoreStickers,
+            "NEWS" => Self::News,
+            "PARTNERED" => Self::Partnered,
+            "PREVIEW_ENABLED" => Self::PreviewEnabled,
+            "RAID_ALERTS_DISABLED" => Self::RaidAlertsDisabled,
+            "ROLE_ICONS" => Self::RoleIcons,
+            "ROLE_SUBSCRIPTIONS_AVAILABLE_FOR_PURCHASE" => {
+                Self::RoleSubscriptionsAvailableForPurchase
+            }
+            "ROLE_SUBSCRIPTIONS_ENABLED" => Self::RoleSubscriptionsEnabled,
+            "TICKETED_EVENTS_ENABLED" => Self::TicketedEventsEnabled,
+            "VANITY_URL" => Self::VanityUrl,
+            "VERIFIED" => Self::Verified,
+            "VIP_REGIONS" => Self::VipRegions,
+            "WELCOME_SCREEN_ENABLED" => Self::WelcomeScreenEnabled,
+            _ => Self::Unknown(feature),
+        }
+    }
+}
+
+/// The server boost level of a guild
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "u8")]
+pub enum PremiumTier {
+    NoTier,
+    Tier1,
+    Tier2,
+    Tier3,
+    Unknown(u8),
+}
+
+impl From<u8> for PremiumTier {
+    fn from(tier: u8) -> Self {
+        match tier {
+            0 => Self::NoTier,
+            1 => Self::Tier1,
+            2 => Self::Tier2,
+            3 => Self::Tier3,
+            _ => Self::Unknown(tier),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "u8")]
+pub enum NsfwLevel {
+    Default,
+    Explicit,
+    Safe,
+    AgeRestricted,
+    Unknown(u8),
+}
+
+impl From<u8> for NsfwLevel {
+    fn from(level: u8) -> Self {
+        match level {
+            0 => Self::Default,
+            1 => Self::Explicit,
+            2 => Self::Safe,
+            3 => Self::AgeRestricted,
+            _ => Self::Unknown(level),
+        }
+    }
+}
+
+/// A user's [connection](https://discord.com/developers/docs/resources/voice#voice-state-object)
+/// to a voice channel.
+#[derive(Debug, Deserialize)]
+pub struct VoiceState {
+    pub guild_id: Option<api::misc::Snowflake>,
+    /// The channel the user is connected to, or `None` when they've disconnected
+    pub channel_id: Option<api::misc::Snowflake>,
+    pub user_id: api::misc::Snowflake,
+    pub member: Option<GuildMember>,
+    pub session_id: String,
+    /// Whether the user has been deafened by the guild
+    pub deaf: bool,
+    /// Whether the user has been muted by the guild
+    pub mute: bool,
+    pub self_deaf: bool,
+    pub self_mute: bool,
+    pub self_stream: Option<bool>,
+    pub self_video: bool,
+    /// Whether the user isn't allowed to speak in a stage channel
+    pub suppress: bool,
+    /// ISO8601 timestamp of when the user requested to speak in a stage channel
+    pub request_to_speak_timestamp: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Guild {
     pub id: api::misc::Snowflake,
     pub name: String,
-    pub permissions: Option<String>,
+    pub owner_id: api::misc::Snowflake,
+    /// The permissions of the bot in the guild, which is only sent when listing the bot's guilds
+    pub permissions: Option<api::permissions::Permissions>,
     pub icon: Option<String>,
     pub icon_hash: Option<String>,
     pub splash: Option<String>,
+    pub discovery_splash: Option<String>,
+    pub banner: Option<String>,
+    pub description: Option<String>,
     pub roles: Vec<Role>,
+    #[serde(default)]
+    pub emojis: Vec<api::emoji::Emoji>,
+    #[serde(default)]
+    pub stickers: Vec<api::sticker::Sticker>,
+    #[serde(default)]
+    pub features: Vec<GuildFeature>,
+    pub afk_channel_id: Option<api::misc::Snowflake>,
     pub afk_timeout: i64,
     pub verification_level: i64,
+    pub default_message_notifications: Option<i64>,
+    pub explicit_content_filter: Option<i64>,
     pub mfa_level: i64,
+    pub nsfw_level: Option<NsfwLevel>,
+    /// The channel that join messages and boost notifications are sent to
+    pub system_channel_id: Option<api::misc::Snowflake>,
+    pub system_channel_flags: Option<u64>,
+    /// The channel where community guilds display their rules
+    pub rules_channel_id: Option<api::misc::Snowflake>,
+    /// The channel where community guilds receive notices from Discord
+    pub public_updates_channel_id: Option<api::misc::Snowflake>,
+    pub vanity_url_code: Option<String>,
+    pub premium_tier: Option<PremiumTier>,
+    /// The number of server boosts the guild has
+    pub premium_subscription_count: Option<u32>,
+    /// The locale of a community guild, such as `en-US`
+    pub preferred_locale: Option<String>,
+    pub max_members: Option<u32>,
+    pub approximate_member_count: Option<u32>,
+    pub approximate_presence_count: Option<u32>,
     pub channels: Option<Vec<api::channel::Channel>>,
+    /// ISO8601 timestamp of when the bot joined the guild. This is only sent in `GUILD_CREATE`.
+    pub joined_at: Option<String>,
+    /// Whether the guild is considered large, in which case `members` only contains online
+    /// members. This is only sent in `GUILD_CREATE`.
+    pub large: Option<bool>,
+    /// The total number of members in the guild. This is only sent in `GUILD_CREATE`.
+    pub member_count: Option<u32>,
+    /// The members in the guild. This is only sent in `GUILD_CREATE`.
+    #[serde(default)]
+    pub members: Vec<GuildMember>,
+    /// The users connected to voice channels. This is only sent in `GUILD_CREATE`.
+    #[serde(default)]
+    pub voice_states: Vec<VoiceState>,
+    /// The presences of the guild's members. This is only sent in `GUILD_CREATE`.
+    #[serde(default)]
+    pub presences: Vec<api::user::Presence>,
+    /// The active threads the bot can see. This is only sent in `GUILD_CREATE`.
+    #[serde(default)]
+    pub threads: Vec<api::channel::Channel>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct GuildMember {
     pub user: Option<api::user::User>,
     pub nick: Option<String>,
@@ -46,7 +409,45 @@ pub struct GuildMember {
     pub deaf: bool,
     pub mute: bool,
     pub pending: Option<bool>,
-    pub permissions: Option<String>,
+    /// The permissions of the member in the channel an interaction was sent from, including
+    /// overwrites. This is only sent with interactions.
+    pub permissions: Option<api::permissions::Permissions>,
+    /// ISO8601 timestamp of when the member's timeout will expire
+    pub communication_disabled_until: Option<String>,
+}
+
+/// Sent through the gateway when a user joins a guild. This requires the `GUILD_MEMBERS` intent.
+#[derive(Clone, Debug, Deserialize)]
+pub struct GuildMemberAdd {
+    pub guild_id: api::misc::Snowflake,
+    #[serde(flatten)]
+    pub member: GuildMember,
+}
+
+/// The [vanity invite](https://discord.com/developers/docs/resources/guild#get-guild-vanity-url)
+/// of a guild that has the `VANITY_URL` feature.
+#[derive(Debug, Deserialize)]
+pub struct VanityUrl {
+    /// The vanity invite code, or `None` if the guild hasn't set one
+    pub code: Option<String>,
+    pub uses: u32,
+}
+
+impl VanityUrl {
+    /// Fetches a guild's vanity invite and how many times it has been used. This requires the
+    /// `MANAGE_GUILD` permission.
+    pub async fn get(config: &api::config::BotConfig, guild_id: api::misc::Snowflake) -> Self {
+        api::base::get(config, format!("guilds/{}/vanity-url", guild_id)).await
+    }
+
+    /// Fetches a guild's vanity invite like [`VanityUrl::get`], returning an error instead of
+    /// panicking when the request fails.
+    pub async fn try_get(
+        config: &api::config::BotConfig,
+        guild_id: api::misc::Snowflake,
+    ) -> Result<Self, api::misc::RequestError> {
+        api::base::try_get(config, format!("guilds/{}/vanity-url", guild_id)).await
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -63,24 +464,499 @@ pub struct GuildMemberUpdate {
     pub pending: Option<bool>,
 }
 
+/// A user that has been [banned](https://discord.com/developers/docs/resources/guild#ban-object)
+/// from a guild.
+#[derive(Debug, Deserialize)]
+pub struct Ban {
+    pub reason: Option<String>,
+    pub user: api::user::User,
+}
+
+/// Sent through the gateway when a user is banned from or unbanned from a guild.
+#[derive(Debug, Deserialize)]
+pub struct GuildBanEvent {
+    pub guild_id: api::misc::Snowflake,
+    pub user: api::user::User,
+}
+
+#[derive(Debug, Serialize)]
+struct BansQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<api::misc::Snowflake>,
+    limit: u16,
+}
+
+#[derive(Debug, Serialize)]
+struct MembersQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<api::misc::Snowflake>,
+    limit: u16,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchMembersQuery {
+    query: String,
+    limit: u16,
+}
+
+/// The most members that can be fetched from a guild in a single request
+const MEMBERS_PAGE_LIMIT: u16 = 1000;
+
+/// The most bans that can be fetched from a guild in a single request
+const BANS_PAGE_LIMIT: u16 = 1000;
+
+/// The most messages that can be deleted from a user when banning them, which is 7 days
+const BAN_DELETE_MESSAGE_SECONDS_LIMIT: u32 = 7 * 24 * 60 * 60;
+
 impl Guild {
-    pub fn get(config: &api::config::BotConfig, guild_id: api::misc::Snowflake) -> Self {
-        api::base::get(config, format!("guilds/{}", guild_id))
+    pub async fn get(config: &api::config::BotConfig, guild_id: api::misc::Snowflake) -> Self {
+        api::base::get(config, format!("guilds/{}", guild_id)).await
+    }
+
+    /// Fetches a page of up to `limit` (at most 1000) members, ordered by user ID, starting with
+    /// the first member whose ID is greater than `after`. This requires the `GUILD_MEMBERS`
+    /// intent.
+    pub async fn members(
+        &self,
+        config: &api::config::BotConfig,
+        after: Option<api::misc::Snowflake>,
+        limit: u16,
+    ) -> Vec<GuildMember> {
+        let query = MembersQuery {
+            after,
+            limit: limit.clamp(1, MEMBERS_PAGE_LIMIT),
+        };
+        api::base::get_with_query(config, format!("guilds/{}/members", self.id), query).await
+    }
+
+    /// Streams every member of the guild, requesting pages of members as the stream is consumed.
+    pub fn all_members<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+    ) -> impl Stream<Item = GuildMember> + 'a {
+        api::base::paginate(
+            None,
+            move |after| self.members(config, after, MEMBERS_PAGE_LIMIT),
+            |_, page: &Vec<GuildMember>| match page.last().and_then(|last| last.user.as_ref()) {
+                Some(last) if page.len() == MEMBERS_PAGE_LIMIT as usize => Some(Some(last.id)),
+                _ => None,
+            },
+        )
     }
 
-    pub fn members(&self, config: &api::config::BotConfig) -> Vec<GuildMember> {
-        api::base::get(config, format!("guilds/{}/members", self.id))
+    /// Finds up to `limit` (at most 1000) members whose username or nickname starts with `query`.
+    ///
+    /// Unlike [`Guild::all_members`], this isn't a stream: the search endpoint doesn't accept a
+    /// cursor, so Discord never returns more than the first 1000 matches. Use a longer `query`
+    /// to narrow down searches that hit the limit.
+    pub async fn search_members(
+        &self,
+        config: &api::config::BotConfig,
+        query: String,
+        limit: u16,
+    ) -> Vec<GuildMember> {
+        let query = SearchMembersQuery {
+            query,
+            limit: limit.clamp(1, MEMBERS_PAGE_LIMIT),
+        };
+        let endpoint = format!("guilds/{}/members/search", self.id);
+        api::base::get_with_query(config, endpoint, query).await
     }
 
-    pub fn member(
+    pub async fn member(
         &self,
         config: &api::config::BotConfig,
         user_id: api::misc::Snowflake,
     ) -> Option<GuildMember> {
-        api::base::get(config, format!("guilds/{}/members/{}", self.id, user_id))
+        api::base::get(config, format!("guilds/{}/members/{}", self.id, user_id)).await
+    }
+
+    /// Creates a new channel in the guild with the settings from `channel`.
+    pub async fn create_channel(
+        &self,
+        config: &api::config::BotConfig,
+        name: String,
+        channel: &api::channel::ChannelBuilder,
+        reason: Option<String>,
+    ) -> api::channel::Channel {
+        let mut channel = channel.clone();
+        channel.name(name);
+        api::base::post(config, format!("guilds/{}/channels", self.id), channel, reason).await
+    }
+
+    /// Moves channels within the guild. Only the channels being moved need to be included.
+    pub async fn reorder_channels(
+        &self,
+        config: &api::config::BotConfig,
+        positions: Vec<api::channel::ChannelPosition>,
+    ) {
+        api::base::patch(config, format!("guilds/{}/channels", self.id), positions, None).await
+    }
+
+    /// Fetches every active thread in the guild that the bot can see.
+    pub async fn active_threads(&self, config: &api::config::BotConfig) -> api::thread::ThreadList {
+        api::base::get(config, format!("guilds/{}/threads/active", self.id)).await
     }
 
-    pub fn roles(&self, config: &api::config::BotConfig) -> Vec<Role> {
-        api::base::get(config, format!("guild/{}/roles", self.id))
+    /// Changes the bot's own nickname in the guild, or resets it when `nick` is `None`. Returns
+    /// the bot's updated member.
+    pub async fn modify_current_member(
+        &self,
+        config: &api::config::BotConfig,
+        nick: Option<String>,
+        reason: Option<String>,
+    ) -> GuildMember {
+        let mut payload = HashMap::new();
+        payload.insert("nick", nick);
+        let endpoint = format!("guilds/{}/members/@me", self.id);
+        api::base::patch(config, endpoint, payload, reason).await
+    }
+
+    /// Removes a member from the guild. They will be able to rejoin with a new invite.
+    pub async fn kick(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = format!("guilds/{}/members/{}", self.id, user_id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    /// Changes the settings of a member, returning the updated member.
+    pub async fn modify_member(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        changes: &ModifyMemberBuilder,
+        reason: Option<String>,
+    ) -> GuildMember {
+        let endpoint = format!("guilds/{}/members/{}", self.id, user_id);
+        api::base::patch(config, endpoint, changes, reason).await
+    }
+
+    /// Times a member out until `until`, which can be at most 28 days in the future, or removes
+    /// their timeout when `until` is `None`. Members that are timed out can't send messages,
+    /// react, join voice channels or speak. Returns the updated member.
+    pub async fn timeout_member(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        until: Option<chrono::DateTime<chrono::Utc>>,
+        reason: Option<String>,
+    ) -> GuildMember {
+        let mut changes = ModifyMemberBuilder::new();
+        changes.communication_disabled_until(until);
+        self.modify_member(config, user_id, &changes, reason).await
+    }
+
+    fn member_role_endpoint(
+        &self,
+        user_id: api::misc::Snowflake,
+        role_id: api::misc::Snowflake,
+    ) -> String {
+        format!("guilds/{}/members/{}/roles/{}", self.id, user_id, role_id)
+    }
+
+    /// Gives a member a role.
+    pub async fn add_member_role(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        role_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = self.member_role_endpoint(user_id, role_id);
+        api::base::put_empty(config, endpoint, reason).await
+    }
+
+    /// Takes a role away from a member.
+    pub async fn remove_member_role(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        role_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = self.member_role_endpoint(user_id, role_id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    /// Bans a user from the guild, deleting the messages they sent within the last
+    /// `delete_message_seconds` (up to 7 days).
+    pub async fn ban(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        delete_message_seconds: u32,
+        reason: Option<String>,
+    ) {
+        let mut payload = HashMap::new();
+        payload.insert(
+            "delete_message_seconds",
+            delete_message_seconds.min(BAN_DELETE_MESSAGE_SECONDS_LIMIT),
+        );
+        let endpoint = format!("guilds/{}/bans/{}", self.id, user_id);
+        api::base::put(config, endpoint, payload, reason).await
+    }
+
+    /// Removes the ban for a user.
+    pub async fn unban(
+        &self,
+        config: &api::config::BotConfig,
+        user_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = format!("guilds/{}/bans/{}", self.id, user_id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    /// Fetches a page of up to `limit` (at most 1000) bans, ordered by user ID, starting with the
+    /// first user whose ID is greater than `after`.
+    pub async fn bans(
+        &self,
+        config: &api::config::BotConfig,
+        after: Option<api::misc::Snowflake>,
+        limit: u16,
+    ) -> Vec<Ban> {
+        let query = BansQuery {
+            after,
+            limit: limit.clamp(1, BANS_PAGE_LIMIT),
+        };
+        api::base::get_with_query(config, format!("guilds/{}/bans", self.id), query).await
+    }
+
+    /// Streams every ban in the guild, requesting pages of bans as the stream is consumed.
+    pub fn all_bans<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+    ) -> impl Stream<Item = Ban> + 'a {
+        api::base::paginate(
+            None,
+            move |after| self.bans(config, after, BANS_PAGE_LIMIT),
+            |_, page: &Vec<Ban>| match page.last() {
+                Some(last) if page.len() == BANS_PAGE_LIMIT as usize => Some(Some(last.user.id)),
+                _ => None,
+            },
+        )
+    }
+
+    pub async fn emojis(&self, config: &api::config::BotConfig) -> Vec<api::emoji::Emoji> {
+        api::base::get(config, format!("guilds/{}/emojis", self.id)).await
+    }
+
+    pub async fn emoji(
+        &self,
+        config: &api::config::BotConfig,
+        emoji_id: api::misc::Snowflake,
+    ) -> api::emoji::Emoji {
+        api::base::get(config, format!("guilds/{}/emojis/{}", self.id, emoji_id)).await
+    }
+
+    /// Uploads a custom emoji from a PNG, JPEG, GIF or WebP `image` of at most 256 KiB, where
+    /// `content_type` is the image's MIME type, such as `image/png`. When `role_ids` isn't empty,
+    /// only members with one of those roles can use the emoji. This requires the
+    /// `CREATE_GUILD_EXPRESSIONS` permission.
+    pub async fn create_emoji(
+        &self,
+        config: &api::config::BotConfig,
+        name: String,
+        content_type: &str,
+        image: &[u8],
+        role_ids: Vec<api::misc::Snowflake>,
+        reason: Option<String>,
+    ) -> api::emoji::Emoji {
+        let emoji = api::emoji::CreateEmoji {
+            name,
+            image: api::misc::image_data_uri(content_type, image),
+            roles: role_ids,
+        };
+        api::base::post(config, format!("guilds/{}/emojis", self.id), emoji, reason).await
+    }
+
+    /// Changes the name or roles of a custom emoji, returning the updated emoji.
+    pub async fn modify_emoji(
+        &self,
+        config: &api::config::BotConfig,
+        emoji_id: api::misc::Snowflake,
+        changes: &api::emoji::ModifyEmojiBuilder,
+        reason: Option<String>,
+    ) -> api::emoji::Emoji {
+        let endpoint = format!("guilds/{}/emojis/{}", self.id, emoji_id);
+        api::base::patch(config, endpoint, changes, reason).await
+    }
+
+    pub async fn delete_emoji(
+        &self,
+        config: &api::config::BotConfig,
+        emoji_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = format!("guilds/{}/emojis/{}", self.id, emoji_id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    pub async fn stickers(&self, config: &api::config::BotConfig) -> Vec<api::sticker::Sticker> {
+        api::base::get(config, format!("guilds/{}/stickers", self.id)).await
+    }
+
+    pub async fn sticker(
+        &self,
+        config: &api::config::BotConfig,
+        sticker_id: api::misc::Snowflake,
+    ) -> api::sticker::Sticker {
+        api::base::get(config, format!("guilds/{}/stickers/{}", self.id, sticker_id)).await
+    }
+
+    /// Uploads a new sticker to the guild. This requires the `CREATE_GUILD_EXPRESSIONS`
+    /// permission.
+    pub async fn create_sticker(
+        &self,
+        config: &api::config::BotConfig,
+        sticker: &api::sticker::CreateStickerBuilder,
+        reason: Option<String>,
+    ) -> api::sticker::Sticker {
+        let endpoint = format!("guilds/{}/stickers", self.id);
+        api::base::post_form(config, endpoint, sticker.form_fields(), sticker.file(), reason).await
+    }
+
+    pub async fn delete_sticker(
+        &self,
+        config: &api::config::BotConfig,
+        sticker_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = format!("guilds/{}/stickers/{}", self.id, sticker_id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    /// Lists the events scheduled in the guild. When `with_user_count` is true, the number of
+    /// users subscribed to each event is included.
+    pub async fn scheduled_events(
+        &self,
+        config: &api::config::BotConfig,
+        with_user_count: bool,
+    ) -> Vec<api::scheduled_event::GuildScheduledEvent> {
+        let query = api::scheduled_event::ScheduledEventsQuery { with_user_count };
+        let endpoint = format!("guilds/{}/scheduled-events", self.id);
+        api::base::get_with_query(config, endpoint, query).await
+    }
+
+    pub async fn scheduled_event(
+        &self,
+        config: &api::config::BotConfig,
+        event_id: api::misc::Snowflake,
+        with_user_count: bool,
+    ) -> api::scheduled_event::GuildScheduledEvent {
+        let query = api::scheduled_event::ScheduledEventsQuery { with_user_count };
+        let endpoint = format!("guilds/{}/scheduled-events/{}", self.id, event_id);
+        api::base::get_with_query(config, endpoint, query).await
+    }
+
+    /// Schedules a new event in the guild. This requires the `MANAGE_EVENTS` or `CREATE_EVENTS`
+    /// permission.
+    pub async fn create_scheduled_event(
+        &self,
+        config: &api::config::BotConfig,
+        event: &api::scheduled_event::ScheduledEventBuilder,
+        reason: Option<String>,
+    ) -> api::scheduled_event::GuildScheduledEvent {
+        let endpoint = format!("guilds/{}/scheduled-events", self.id);
+        api::base::post(config, endpoint, event, reason).await
+    }
+
+    /// Lists the webhooks in every channel of the guild. This requires the `MANAGE_WEBHOOKS`
+    /// permission.
+    pub async fn webhooks(&self, config: &api::config::BotConfig) -> Vec<api::webhook::Webhook> {
+        api::base::get(config, format!("guilds/{}/webhooks", self.id)).await
+    }
+
+    /// Lists the invites to every channel in the guild. This requires the `MANAGE_GUILD`
+    /// permission.
+    pub async fn invites(&self, config: &api::config::BotConfig) -> Vec<api::invite::Invite> {
+        api::invite::Invite::list_for_guild(config, self.id).await
+    }
+
+    /// Reads a page of the guild's audit log, newest entries first, filtered by `query`. This
+    /// requires the `VIEW_AUDIT_LOG` permission.
+    pub async fn audit_log(
+        &self,
+        config: &api::config::BotConfig,
+        query: &api::audit_log::AuditLogQuery,
+    ) -> api::audit_log::AuditLog {
+        let endpoint = format!("guilds/{}/audit-logs", self.id);
+        api::base::get_with_query(config, endpoint, query).await
+    }
+
+    /// Streams every audit log entry matching `query`, from the newest to the oldest, requesting
+    /// pages of entries as the stream is consumed. The `limit` set on `query` is ignored, so that
+    /// the stream continues until it reaches the oldest entry or the one set with `after`.
+    pub fn all_audit_log_entries<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+        query: &api::audit_log::AuditLogQuery,
+    ) -> impl Stream<Item = api::audit_log::AuditLogEntry> + 'a {
+        use api::audit_log::AUDIT_LOG_PAGE_LIMIT;
+
+        let mut query = query.clone();
+        query.limit(AUDIT_LOG_PAGE_LIMIT);
+        api::base::paginate(
+            query,
+            move |query| async move { self.audit_log(config, &query).await.audit_log_entries },
+            |mut query, page: &Vec<api::audit_log::AuditLogEntry>| match page.last() {
+                Some(oldest) if page.len() == AUDIT_LOG_PAGE_LIMIT as usize => {
+                    query.before(oldest.id);
+                    Some(query)
+                }
+                _ => None,
+            },
+        )
+    }
+
+    pub async fn roles(&self, config: &api::config::BotConfig) -> Vec<Role> {
+        api::base::get(config, format!("guilds/{}/roles", self.id)).await
+    }
+
+    /// Creates a new role with the settings from `role`. Any unset settings use Discord's
+    /// defaults, which is a role named "new role" with the permissions of `@everyone`.
+    pub async fn create_role(
+        &self,
+        config: &api::config::BotConfig,
+        role: &RoleBuilder,
+        reason: Option<String>,
+    ) -> Role {
+        api::base::post(config, format!("guilds/{}/roles", self.id), role, reason).await
+    }
+
+    /// Changes the settings of a role, returning the updated role.
+    pub async fn modify_role(
+        &self,
+        config: &api::config::BotConfig,
+        role_id: api::misc::Snowflake,
+        changes: &RoleBuilder,
+        reason: Option<String>,
+    ) -> Role {
+        let endpoint = format!("guilds/{}/roles/{}", self.id, role_id);
+        api::base::patch(config, endpoint, changes, reason).await
+    }
+
+    pub async fn delete_role(
+        &self,
+        config: &api::config::BotConfig,
+        role_id: api::misc::Snowflake,
+        reason: Option<String>,
+    ) {
+        let endpoint = format!("guilds/{}/roles/{}", self.id, role_id);
+        api::base::delete(config, endpoint, reason).await
+    }
+
+    /// Moves roles within the guild's role hierarchy, returning every role in the guild.
+    pub async fn reorder_roles(
+        &self,
+        config: &api::config::BotConfig,
+        positions: Vec<RolePosition>,
+        reason: Option<String>,
+    ) -> Vec<Role> {
+        api::base::patch(config, format!("guilds/{}/roles", self.id), positions, reason).await
     }
 }
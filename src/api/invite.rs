@@ -0,0 +1,205 @@
+use crate::api;
+
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// What a voice channel invite opens once it has been accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum InviteTargetType {
+    /// A user's stream in the channel
+    Stream = 1,
+    /// An embedded application, such as an activity
+    EmbeddedApplication = 2,
+}
+
+/// The part of a guild that is sent with an invite, which is visible to users that haven't
+/// joined the guild yet.
+#[derive(Debug, Deserialize)]
+pub struct InviteGuild {
+    pub id: api::misc::Snowflake,
+    pub name: String,
+    pub icon: Option<String>,
+    pub splash: Option<String>,
+    pub banner: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub features: Vec<api::guild::GuildFeature>,
+    pub verification_level: Option<i64>,
+    pub vanity_url_code: Option<String>,
+    pub nsfw_level: Option<api::guild::NsfwLevel>,
+    pub premium_subscription_count: Option<u32>,
+}
+
+/// An [invite](https://discord.com/developers/docs/resources/invite#invite-object) to a guild
+/// or group direct message channel.
+#[derive(Debug, Deserialize)]
+pub struct Invite {
+    pub code: String,
+    pub guild: Option<InviteGuild>,
+    /// The channel the invite is for, which only includes its `id`, `name` and `type`
+    pub channel: Option<api::channel::Channel>,
+    pub inviter: Option<api::user::User>,
+    pub target_type: Option<InviteTargetType>,
+    /// The user whose stream the invite opens
+    pub target_user: Option<api::user::User>,
+    /// An approximate count of the online members in the guild, which is only sent when the
+    /// invite is fetched with counts
+    pub approximate_presence_count: Option<u32>,
+    /// An approximate count of the members in the guild, which is only sent when the invite is
+    /// fetched with counts
+    pub approximate_member_count: Option<u32>,
+    /// ISO8601 timestamp of when the invite expires, or `None` if it never expires
+    pub expires_at: Option<String>,
+    /// The number of times the invite has been used. This and the rest of the fields are only
+    /// sent when listing the invites of a channel or guild.
+    pub uses: Option<u32>,
+    /// The number of times the invite can be used, where 0 is unlimited
+    pub max_uses: Option<u32>,
+    /// The number of seconds after creation that the invite expires, where 0 never expires
+    pub max_age: Option<u32>,
+    /// Whether the invite only grants temporary membership, which is removed when the member
+    /// disconnects without having been given a role
+    pub temporary: Option<bool>,
+    /// ISO8601 timestamp of when the invite was created
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct GetInviteQuery {
+    with_counts: bool,
+    with_expiration: bool,
+}
+
+impl Invite {
+    /// Fetches an invite by its code. When `with_counts` is true, the approximate member counts
+    /// of the guild are included.
+    pub async fn get(config: &api::config::BotConfig, code: &str, with_counts: bool) -> Self {
+        let query = GetInviteQuery {
+            with_counts,
+            with_expiration: true,
+        };
+        api::base::get_with_query(config, format!("invites/{}", code), query).await
+    }
+
+    /// Lists the invites to every channel in a guild. This requires the `MANAGE_GUILD`
+    /// permission.
+    pub async fn list_for_guild(
+        config: &api::config::BotConfig,
+        guild_id: api::misc::Snowflake,
+    ) -> Vec<Self> {
+        api::base::get(config, format!("guilds/{}/invites", guild_id)).await
+    }
+
+    /// Lists the invites to every channel in a guild like [`Invite::list_for_guild`], returning
+    /// an error instead of panicking when the request fails, such as when the bot is missing
+    /// the `MANAGE_GUILD` permission.
+    pub async fn try_list_for_guild(
+        config: &api::config::BotConfig,
+        guild_id: api::misc::Snowflake,
+    ) -> Result<Vec<Self>, api::misc::RequestError> {
+        api::base::try_get(config, format!("guilds/{}/invites", guild_id)).await
+    }
+
+    /// Deletes the invite so that it can no longer be used, returning the deleted invite. This
+    /// requires the `MANAGE_CHANNELS` permission in the invite's channel, or `MANAGE_GUILD`.
+    pub async fn delete(&self, config: &api::config::BotConfig, reason: Option<String>) -> Self {
+        api::base::delete(config, format!("invites/{}", self.code), reason).await
+    }
+}
+
+/// Used to create an invite to a channel with
+/// [`Channel::create_invite`](api::channel::Channel::create_invite). Unset settings use
+/// Discord's defaults, which is an invite that expires after 24 hours with unlimited uses.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateInviteBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_age: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_uses: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temporary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_type: Option<InviteTargetType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_user_id: Option<api::misc::Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_application_id: Option<api::misc::Snowflake>,
+}
+
+impl CreateInviteBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of seconds until the invite expires, up to 604800 (7 days), where 0 never
+    /// expires.
+    pub fn max_age(&mut self, seconds: u32) -> &mut Self {
+        self.max_age = Some(seconds.min(7 * 24 * 60 * 60));
+        self
+    }
+
+    /// The number of times the invite can be used, up to 100, where 0 is unlimited.
+    pub fn max_uses(&mut self, uses: u8) -> &mut Self {
+        self.max_uses = Some(uses.min(100));
+        self
+    }
+
+    /// Whether the invite only grants temporary membership.
+    pub fn temporary(&mut self, temporary: bool) -> &mut Self {
+        self.temporary = Some(temporary);
+        self
+    }
+
+    /// Whether to always create a new invite, rather than reusing a similar existing one.
+    pub fn unique(&mut self, unique: bool) -> &mut Self {
+        self.unique = Some(unique);
+        self
+    }
+
+    /// Makes a voice channel invite open a user's stream.
+    pub fn target_user(&mut self, user_id: api::misc::Snowflake) -> &mut Self {
+        self.target_type = Some(InviteTargetType::Stream);
+        self.target_user_id = Some(user_id);
+        self.target_application_id = None;
+        self
+    }
+
+    /// Makes a voice channel invite open an embedded application, which must have the
+    /// `EMBEDDED` flag.
+    pub fn target_application(&mut self, application_id: api::misc::Snowflake) -> &mut Self {
+        self.target_type = Some(InviteTargetType::EmbeddedApplication);
+        self.target_user_id = None;
+        self.target_application_id = Some(application_id);
+        self
+    }
+}
+
+/// Sent through the gateway when an invite is created. This requires the `MANAGE_CHANNELS`
+/// permission for the channel.
+#[derive(Debug, Deserialize)]
+pub struct InviteCreate {
+    pub channel_id: api::misc::Snowflake,
+    pub code: String,
+    /// ISO8601 timestamp of when the invite was created
+    pub created_at: String,
+    pub guild_id: Option<api::misc::Snowflake>,
+    pub inviter: Option<api::user::User>,
+    pub max_age: u32,
+    pub max_uses: u32,
+    pub target_type: Option<InviteTargetType>,
+    pub target_user: Option<api::user::User>,
+    pub temporary: bool,
+    /// This is always 0
+    pub uses: u32,
+}
+
+/// Sent through the gateway when an invite is deleted or expires.
+#[derive(Debug, Deserialize)]
+pub struct InviteDelete {
+    pub channel_id: api::misc::Snowflake,
+    pub guild_id: Option<api::misc::Snowflake>,
+    pub code: String,
+}
@@ -1,5 +1,6 @@
 use crate::api;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use serde_repr::Deserialize_repr;
 
 #[derive(Debug, Deserialize_repr)]
@@ -49,76 +50,251 @@ pub struct Message {
     pub message_type: MessageType,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedFooter {
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_icon_url: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedImage {
     pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<i64>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedThumbnail {
     pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<i64>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedVideo {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<i64>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedProvider {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedAuthor {
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_icon_url: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedField {
     pub name: String,
     pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inline: Option<bool>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MessageEmbed {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
-    pub timestamp: Option<i64>,
+    /// ISO8601 timestamp displayed in the footer of the embed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub footer: Option<EmbedFooter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<EmbedImage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumbnail: Option<EmbedThumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub video: Option<EmbedVideo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub provider: Option<EmbedProvider>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<EmbedAuthor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<EmbedField>,
 }
 
+/// [Limits](https://discord.com/developers/docs/resources/channel#embed-object-embed-limits)
+/// that Discord places on the size of embeds.
+pub const EMBED_TITLE_LIMIT: usize = 256;
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+pub const EMBED_FIELD_COUNT_LIMIT: usize = 25;
+pub const EMBED_FIELD_NAME_LIMIT: usize = 256;
+pub const EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+pub const EMBED_FOOTER_TEXT_LIMIT: usize = 2048;
+pub const EMBED_AUTHOR_NAME_LIMIT: usize = 256;
+/// The total number of characters across every embed in a single message.
+pub const EMBED_TOTAL_CHARACTER_LIMIT: usize = 6000;
+/// The number of embeds that can be sent in a single message.
+pub const MESSAGE_EMBED_LIMIT: usize = 10;
+
+/// Returned when an embed would exceed one of Discord's limits. Each variant contains the size
+/// that the limit was exceeded with.
+#[derive(Debug, PartialEq, Eq)]
+pub enum EmbedLimitError {
+    TitleTooLong(usize),
+    DescriptionTooLong(usize),
+    TooManyFields(usize),
+    FieldNameTooLong(usize),
+    FieldValueTooLong(usize),
+    FooterTextTooLong(usize),
+    AuthorNameTooLong(usize),
+    TooManyCharacters(usize),
+    TooManyEmbeds(usize),
+}
+
+impl fmt::Display for EmbedLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (what, size, limit) = match self {
+            Self::TitleTooLong(n) => ("title length", n, EMBED_TITLE_LIMIT),
+            Self::DescriptionTooLong(n) => ("description length", n, EMBED_DESCRIPTION_LIMIT),
+            Self::TooManyFields(n) => ("field count", n, EMBED_FIELD_COUNT_LIMIT),
+            Self::FieldNameTooLong(n) => ("field name length", n, EMBED_FIELD_NAME_LIMIT),
+            Self::FieldValueTooLong(n) => ("field value length", n, EMBED_FIELD_VALUE_LIMIT),
+            Self::FooterTextTooLong(n) => ("footer text length", n, EMBED_FOOTER_TEXT_LIMIT),
+            Self::AuthorNameTooLong(n) => ("author name length", n, EMBED_AUTHOR_NAME_LIMIT),
+            Self::TooManyCharacters(n) => ("total characters", n, EMBED_TOTAL_CHARACTER_LIMIT),
+            Self::TooManyEmbeds(n) => ("embeds per message", n, MESSAGE_EMBED_LIMIT),
+        };
+        write!(f, "Embed {} of {} exceeds the limit of {}", what, size, limit)
+    }
+}
+
+impl std::error::Error for EmbedLimitError {}
+
+fn check_length(
+    value: Option<&String>,
+    limit: usize,
+    error: fn(usize) -> EmbedLimitError,
+) -> Result<(), EmbedLimitError> {
+    match value.map(|v| v.chars().count()) {
+        Some(length) if length > limit => Err(error(length)),
+        _ => Ok(()),
+    }
+}
+
+impl MessageEmbed {
+    /// The number of characters in this embed that count towards the total character limit.
+    pub fn character_count(&self) -> usize {
+        let count = |value: Option<&String>| value.map_or(0, |v| v.chars().count());
+        count(self.title.as_ref())
+            + count(self.description.as_ref())
+            + count(self.footer.as_ref().map(|footer| &footer.text))
+            + count(self.author.as_ref().map(|author| &author.name))
+            + self
+                .fields
+                .iter()
+                .map(|field| field.name.chars().count() + field.value.chars().count())
+                .sum::<usize>()
+    }
+
+    /// Checks that this embed fits within the limits Discord places on a single embed.
+    pub fn validate(&self) -> Result<(), EmbedLimitError> {
+        check_length(self.title.as_ref(), EMBED_TITLE_LIMIT, EmbedLimitError::TitleTooLong)?;
+        check_length(
+            self.description.as_ref(),
+            EMBED_DESCRIPTION_LIMIT,
+            EmbedLimitError::DescriptionTooLong,
+        )?;
+        check_length(
+            self.footer.as_ref().map(|footer| &footer.text),
+            EMBED_FOOTER_TEXT_LIMIT,
+            EmbedLimitError::FooterTextTooLong,
+        )?;
+        check_length(
+            self.author.as_ref().map(|author| &author.name),
+            EMBED_AUTHOR_NAME_LIMIT,
+            EmbedLimitError::AuthorNameTooLong,
+        )?;
+
+        if self.fields.len() > EMBED_FIELD_COUNT_LIMIT {
+            return Err(EmbedLimitError::TooManyFields(self.fields.len()));
+        }
+        for field in &self.fields {
+            let name_length = field.name.chars().count();
+            if name_length > EMBED_FIELD_NAME_LIMIT {
+                return Err(EmbedLimitError::FieldNameTooLong(name_length));
+            }
+            let value_length = field.value.chars().count();
+            if value_length > EMBED_FIELD_VALUE_LIMIT {
+                return Err(EmbedLimitError::FieldValueTooLong(value_length));
+            }
+        }
+
+        let total = self.character_count();
+        if total > EMBED_TOTAL_CHARACTER_LIMIT {
+            return Err(EmbedLimitError::TooManyCharacters(total));
+        }
+        Ok(())
+    }
+}
+
+/// Adds `embed` to the embeds of a message, checking the limits on both the embed itself and
+/// on all of the embeds within a single message.
+pub fn push_embed(
+    embeds: &mut Vec<MessageEmbed>,
+    embed: MessageEmbed,
+) -> Result<(), EmbedLimitError> {
+    embed.validate()?;
+    if embeds.len() >= MESSAGE_EMBED_LIMIT {
+        return Err(EmbedLimitError::TooManyEmbeds(embeds.len() + 1));
+    }
+    let total = embeds
+        .iter()
+        .map(|embed| embed.character_count())
+        .sum::<usize>()
+        + embed.character_count();
+    if total > EMBED_TOTAL_CHARACTER_LIMIT {
+        return Err(EmbedLimitError::TooManyCharacters(total));
+    }
+
+    embeds.push(embed);
+    Ok(())
+}
+
 pub struct EmbedBuilder(MessageEmbed);
 
+impl Default for EmbedBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EmbedBuilder {
     pub fn new() -> Self {
         Self(MessageEmbed {
@@ -148,11 +324,12 @@ impl EmbedBuilder {
     }
 
     pub fn url(&mut self, u: String) -> &mut Self {
-        self.0.title = Some(u);
+        self.0.url = Some(u);
         self
     }
 
-    pub fn timestamp(&mut self, t: i64) -> &mut Self {
+    /// Sets the ISO8601 timestamp shown in the footer of the embed
+    pub fn timestamp(&mut self, t: String) -> &mut Self {
         self.0.timestamp = Some(t);
         self
     }
@@ -161,4 +338,162 @@ impl EmbedBuilder {
         self.0.color = Some(c);
         self
     }
+
+    pub fn footer(&mut self, text: String, icon_url: Option<String>) -> &mut Self {
+        self.0.footer = Some(EmbedFooter {
+            text,
+            icon_url,
+            proxy_icon_url: None,
+        });
+        self
+    }
+
+    /// Sets the large image of the embed. Files uploaded with the message can be used by passing
+    /// their `attachment://` URL.
+    pub fn image(&mut self, url: String) -> &mut Self {
+        self.0.image = Some(EmbedImage {
+            url,
+            proxy_url: None,
+            height: None,
+            width: None,
+        });
+        self
+    }
+
+    pub fn thumbnail(&mut self, url: String) -> &mut Self {
+        self.0.thumbnail = Some(EmbedThumbnail {
+            url,
+            proxy_url: None,
+            height: None,
+            width: None,
+        });
+        self
+    }
+
+    pub fn author(
+        &mut self,
+        name: String,
+        url: Option<String>,
+        icon_url: Option<String>,
+    ) -> &mut Self {
+        self.0.author = Some(EmbedAuthor {
+            name,
+            url,
+            icon_url,
+            proxy_icon_url: None,
+        });
+        self
+    }
+
+    pub fn field(&mut self, name: String, value: String, inline: bool) -> &mut Self {
+        self.0.fields.push(EmbedField {
+            name,
+            value,
+            inline: Some(inline),
+        });
+        self
+    }
+
+    /// Finishes the embed, checking that it fits within Discord's limits.
+    pub fn build(&self) -> Result<MessageEmbed, EmbedLimitError> {
+        self.0.validate()?;
+        Ok(self.0.clone())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AllowedMentionType {
+    Users,
+    Roles,
+    Everyone,
+}
+
+/// [Controls](https://discord.com/developers/docs/resources/channel#allowed-mentions-object)
+/// which mentions in a message will actually notify the people being mentioned.
+///
+/// An empty `AllowedMentions` (from [`AllowedMentions::new`]) suppresses every mention, while the
+/// [`Default`] only allows users to be pinged, so that echoing user-provided text can never ping
+/// `@everyone`, `@here` or a role. The default used for every message sent with a config can be
+/// changed through the `allowed_mentions` key of the [`BotConfig`](api::config::BotConfig).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AllowedMentions {
+    #[serde(default)]
+    parse: Vec<AllowedMentionType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    users: Vec<api::misc::Snowflake>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<api::misc::Snowflake>,
+    #[serde(default)]
+    replied_user: bool,
+}
+
+impl Default for AllowedMentions {
+    fn default() -> Self {
+        Self {
+            parse: vec![AllowedMentionType::Users],
+            users: Vec::new(),
+            roles: Vec::new(),
+            replied_user: true,
+        }
+    }
+}
+
+impl AllowedMentions {
+    pub fn new() -> Self {
+        Self {
+            parse: Vec::new(),
+            users: Vec::new(),
+            roles: Vec::new(),
+            replied_user: false,
+        }
+    }
+
+    fn parse(&mut self, mention_type: AllowedMentionType) -> &mut Self {
+        if !self.parse.contains(&mention_type) {
+            self.parse.push(mention_type);
+        }
+        self
+    }
+
+    /// Allows every user mentioned in the message to be pinged. This replaces any users
+    /// previously allowed with [`AllowedMentions::user`].
+    pub fn parse_users(&mut self) -> &mut Self {
+        self.users.clear();
+        self.parse(AllowedMentionType::Users)
+    }
+
+    /// Allows every role mentioned in the message to be pinged. This replaces any roles
+    /// previously allowed with [`AllowedMentions::role`].
+    pub fn parse_roles(&mut self) -> &mut Self {
+        self.roles.clear();
+        self.parse(AllowedMentionType::Roles)
+    }
+
+    /// Allows `@everyone` and `@here` to ping.
+    pub fn parse_everyone(&mut self) -> &mut Self {
+        self.parse(AllowedMentionType::Everyone)
+    }
+
+    /// Allows a specific user to be pinged. Discord rejects messages that both parse users and
+    /// list them explicitly, so this stops parsing every user mention.
+    pub fn user(&mut self, user_id: api::misc::Snowflake) -> &mut Self {
+        self.parse.retain(|t| *t != AllowedMentionType::Users);
+        self.users.push(user_id);
+        self
+    }
+
+    /// Allows a specific role to be pinged. Discord rejects messages that both parse roles and
+    /// list them explicitly, so this stops parsing every role mention.
+    pub fn role(&mut self, role_id: api::misc::Snowflake) -> &mut Self {
+        self.parse.retain(|t| *t != AllowedMentionType::Roles);
+        self.roles.push(role_id);
+        self
+    }
+
+    /// Whether the author of the message being replied to should be pinged.
+    pub fn replied_user(&mut self, replied_user: bool) -> &mut Self {
+        self.replied_user = replied_user;
+        self
+    }
 }
@@ -10,7 +10,7 @@ use std::{fmt, hash::Hash, ops::{Deref, DerefMut}};
 /// - The internal ID of the worker that generated it.
 /// - The internal ID of the process that generated it.
 /// - And an internal incrementing ID.
-#[derive(Debug, Serialize, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Serialize, Hash, PartialEq, Eq)]
 pub struct Snowflake(u64);
 
 impl Deref for Snowflake {
@@ -27,6 +27,12 @@ impl DerefMut for Snowflake {
     }
 }
 
+impl From<u64> for Snowflake {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
 impl fmt::Display for Snowflake {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -92,6 +98,50 @@ impl<'de> Deserialize<'de> for Snowflake {
     }
 }
 
+/// The reasons that a request to the Discord API can fail.
+#[derive(Debug)]
+pub enum RequestError {
+    /// The request couldn't be sent, or Discord responded with an error status
+    Http(reqwest::Error),
+    /// The response didn't match the type it was parsed as
+    Json(serde_json::Error),
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Http(error)
+    }
+}
+
+impl From<serde_json::Error> for RequestError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(error) => write!(f, "Request failed: {}", error),
+            Self::Json(error) => write!(f, "Invalid response: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// Encodes an image as a [data URI](https://discord.com/developers/docs/reference#image-data),
+/// which is how images such as role icons and emojis are uploaded. The `content_type` should be
+/// one of `image/png`, `image/jpeg`, `image/gif` or `image/webp`.
+pub fn image_data_uri(content_type: &str, image: &[u8]) -> String {
+    use base64::Engine;
+    format!(
+        "data:{};base64,{}",
+        content_type,
+        base64::engine::general_purpose::STANDARD.encode(image)
+    )
+}
+
 /// An [Application](https://discord.com/developers/docs/resources/application) within Discord and
 /// describes meta-information about the bot.
 #[derive(Debug, Deserialize)]
@@ -121,8 +171,8 @@ pub struct BotGateway {
 }
 
 impl BotGateway {
-    pub fn get(config: &api::config::BotConfig) -> Self {
-        api::base::get(config, String::from("gateway/bot"))
+    pub async fn get(config: &api::config::BotConfig) -> Self {
+        api::base::get(config, String::from("gateway/bot")).await
     }
 }
 
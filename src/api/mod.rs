@@ -1,8 +1,18 @@
 mod base;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod audit_log;
 pub mod channel;
 pub mod config;
+pub mod emoji;
 pub mod guild;
 pub mod interaction;
+pub mod invite;
 pub mod message;
 pub mod misc;
+pub mod permissions;
+pub mod scheduled_event;
+pub mod sticker;
+pub mod thread;
 pub mod user;
+pub mod webhook;
@@ -0,0 +1,378 @@
+use crate::api;
+
+use bitflags::bitflags;
+use serde::de;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+bitflags! {
+    /// The [permissions](https://discord.com/developers/docs/topics/permissions) that can be
+    /// granted to roles and members, either across a guild or within a single channel.
+    #[derive(Default)]
+    pub struct Permissions: u64 {
+        const CREATE_INSTANT_INVITE = 1 << 0;
+        const KICK_MEMBERS = 1 << 1;
+        const BAN_MEMBERS = 1 << 2;
+        /// Grants every permission and bypasses channel permission overwrites
+        const ADMINISTRATOR = 1 << 3;
+        const MANAGE_CHANNELS = 1 << 4;
+        const MANAGE_GUILD = 1 << 5;
+        const ADD_REACTIONS = 1 << 6;
+        const VIEW_AUDIT_LOG = 1 << 7;
+        const PRIORITY_SPEAKER = 1 << 8;
+        const STREAM = 1 << 9;
+        const VIEW_CHANNEL = 1 << 10;
+        const SEND_MESSAGES = 1 << 11;
+        const SEND_TTS_MESSAGES = 1 << 12;
+        const MANAGE_MESSAGES = 1 << 13;
+        const EMBED_LINKS = 1 << 14;
+        const ATTACH_FILES = 1 << 15;
+        const READ_MESSAGE_HISTORY = 1 << 16;
+        const MENTION_EVERYONE = 1 << 17;
+        const USE_EXTERNAL_EMOJIS = 1 << 18;
+        const VIEW_GUILD_INSIGHTS = 1 << 19;
+        const CONNECT = 1 << 20;
+        const SPEAK = 1 << 21;
+        const MUTE_MEMBERS = 1 << 22;
+        const DEAFEN_MEMBERS = 1 << 23;
+        const MOVE_MEMBERS = 1 << 24;
+        const USE_VAD = 1 << 25;
+        const CHANGE_NICKNAME = 1 << 26;
+        const MANAGE_NICKNAMES = 1 << 27;
+        const MANAGE_ROLES = 1 << 28;
+        const MANAGE_WEBHOOKS = 1 << 29;
+        const MANAGE_GUILD_EXPRESSIONS = 1 << 30;
+        const USE_APPLICATION_COMMANDS = 1 << 31;
+        const REQUEST_TO_SPEAK = 1 << 32;
+        const MANAGE_EVENTS = 1 << 33;
+        const MANAGE_THREADS = 1 << 34;
+        const CREATE_PUBLIC_THREADS = 1 << 35;
+        const CREATE_PRIVATE_THREADS = 1 << 36;
+        const USE_EXTERNAL_STICKERS = 1 << 37;
+        const SEND_MESSAGES_IN_THREADS = 1 << 38;
+        const USE_EMBEDDED_ACTIVITIES = 1 << 39;
+        /// Allows timing out members
+        const MODERATE_MEMBERS = 1 << 40;
+        const VIEW_CREATOR_MONETIZATION_ANALYTICS = 1 << 41;
+        const USE_SOUNDBOARD = 1 << 42;
+        const CREATE_GUILD_EXPRESSIONS = 1 << 43;
+        const CREATE_EVENTS = 1 << 44;
+        const USE_EXTERNAL_SOUNDS = 1 << 45;
+        const SEND_VOICE_MESSAGES = 1 << 46;
+        const SET_VOICE_CHANNEL_STATUS = 1 << 48;
+        const SEND_POLLS = 1 << 49;
+        const USE_EXTERNAL_APPS = 1 << 50;
+    }
+}
+
+/// Discord sends permissions as strings, since they can be larger than JavaScript can represent
+/// as a number.
+impl Serialize for Permissions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.bits().to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Permissions {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: de::Deserializer<'de>
+    {
+        struct PermissionsVisitor;
+
+        impl<'de> de::Visitor<'de> for PermissionsVisitor {
+            type Value = Permissions;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("Permissions as a number or string")
+            }
+
+            fn visit_u64<E>(self, bits: u64) -> Result<Self::Value, E>
+                where E: de::Error
+            {
+                // Permissions we don't know about yet are dropped rather than rejected
+                Ok(Permissions::from_bits_truncate(bits))
+            }
+
+            fn visit_str<E>(self, bits: &str) -> Result<Self::Value, E>
+                where E: de::Error
+            {
+                bits.parse().map(Permissions::from_bits_truncate).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PermissionsVisitor)
+    }
+}
+
+/// The permissions that a member who has been timed out keeps.
+const TIMED_OUT_PERMISSIONS: Permissions = Permissions::from_bits_truncate(
+    Permissions::VIEW_CHANNEL.bits() | Permissions::READ_MESSAGE_HISTORY.bits(),
+);
+
+/// The permissions that depend on `SEND_MESSAGES`, which a member loses in channels where they
+/// can't send messages.
+const SEND_MESSAGES_DEPENDENTS: Permissions = Permissions::from_bits_truncate(
+    Permissions::MENTION_EVERYONE.bits()
+        | Permissions::SEND_TTS_MESSAGES.bits()
+        | Permissions::ATTACH_FILES.bits()
+        | Permissions::EMBED_LINKS.bits(),
+);
+
+/// Whether the member is currently [timed out](https://discord.com/developers/docs/resources/guild#modify-guild-member),
+/// which is the case until their `communication_disabled_until` timestamp has passed.
+fn is_timed_out(member: &api::guild::GuildMember) -> bool {
+    member
+        .communication_disabled_until
+        .as_ref()
+        .and_then(|until| chrono::DateTime::parse_from_rfc3339(until).ok())
+        .is_some_and(|until| until > chrono::Utc::now())
+}
+
+/// Computes the permissions a member has across the guild, from the `@everyone` role and each of
+/// the member's roles. The guild owner and administrators have every permission.
+///
+/// The member's `user` needs to be present to determine whether they own the guild.
+pub fn guild_permissions(
+    guild: &api::guild::Guild,
+    member: &api::guild::GuildMember,
+) -> Permissions {
+    let user_id = member.user.as_ref().map(|user| user.id);
+    if user_id == Some(guild.owner_id) {
+        return Permissions::all();
+    }
+
+    // The ID of the @everyone role is the same as the ID of the guild
+    let permissions = guild
+        .roles
+        .iter()
+        .filter(|role| role.id == guild.id || member.role_ids.contains(&role.id))
+        .fold(Permissions::empty(), |permissions, role| permissions | role.permissions);
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+
+    if is_timed_out(member) {
+        permissions & TIMED_OUT_PERMISSIONS
+    } else {
+        permissions
+    }
+}
+
+/// Computes the permissions a member has within a channel, applying the channel's permission
+/// overwrites on top of their [guild permissions](guild_permissions). Overwrites are applied in
+/// order: first for `@everyone`, then for all of the member's roles together, and finally for the
+/// member themselves. Permissions that are implicitly denied, such as every permission in a
+/// channel the member can't view, are then removed.
+pub fn channel_permissions(
+    guild: &api::guild::Guild,
+    member: &api::guild::GuildMember,
+    channel: &api::channel::Channel,
+) -> Permissions {
+    use api::channel::PermissionOverwriteType;
+
+    let mut permissions = guild_permissions(guild, member);
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return permissions;
+    }
+
+    let overwrites = &channel.permission_overwrites;
+    if let Some(everyone) = overwrites.iter().find(|overwrite| overwrite.id == guild.id) {
+        permissions.remove(everyone.deny);
+        permissions.insert(everyone.allow);
+    }
+
+    let (allow, deny) = overwrites
+        .iter()
+        .filter(|overwrite| overwrite.overwrite_type == PermissionOverwriteType::Role)
+        .filter(|overwrite| member.role_ids.contains(&overwrite.id))
+        .fold((Permissions::empty(), Permissions::empty()), |(allow, deny), overwrite| {
+            (allow | overwrite.allow, deny | overwrite.deny)
+        });
+    permissions.remove(deny);
+    permissions.insert(allow);
+
+    let user_id = member.user.as_ref().map(|user| user.id);
+    if let Some(member_overwrite) = overwrites.iter().find(|overwrite| {
+        overwrite.overwrite_type == PermissionOverwriteType::Member
+            && Some(overwrite.id) == user_id
+    }) {
+        permissions.remove(member_overwrite.deny);
+        permissions.insert(member_overwrite.allow);
+    }
+
+    // Members have no permissions in channels they can't see, and lose the permissions that
+    // depend on sending messages in channels where they can't send them
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Permissions::empty();
+    }
+    if !permissions.contains(Permissions::SEND_MESSAGES) {
+        permissions.remove(SEND_MESSAGES_DEPENDENTS);
+    }
+
+    // Timeouts take priority over anything the overwrites allow
+    if is_timed_out(member) {
+        permissions & TIMED_OUT_PERMISSIONS
+    } else {
+        permissions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const GUILD_ID: u64 = 1;
+    const OWNER_ID: u64 = 2;
+    const USER_ID: u64 = 3;
+    const ROLE_ID: u64 = 10;
+
+    fn role(id: u64, permissions: Permissions) -> serde_json::Value {
+        json!({
+            "id": id.to_string(),
+            "name": "role",
+            "color": 0,
+            "hoist": false,
+            "position": 0,
+            "permissions": permissions.bits().to_string(),
+            "managed": false,
+            "mentionable": false,
+        })
+    }
+
+    fn guild(everyone: Permissions, role_permissions: Permissions) -> api::guild::Guild {
+        serde_json::from_value(json!({
+            "id": GUILD_ID.to_string(),
+            "name": "guild",
+            "owner_id": OWNER_ID.to_string(),
+            "roles": [role(GUILD_ID, everyone), role(ROLE_ID, role_permissions)],
+            "afk_timeout": 300,
+            "verification_level": 0,
+            "mfa_level": 0,
+        }))
+        .unwrap()
+    }
+
+    fn member(user_id: u64, timed_out_until: Option<String>) -> api::guild::GuildMember {
+        serde_json::from_value(json!({
+            "user": { "id": user_id.to_string(), "username": "user", "discriminator": "0" },
+            "roles": [ROLE_ID.to_string()],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+            "communication_disabled_until": timed_out_until,
+        }))
+        .unwrap()
+    }
+
+    fn channel(overwrites: serde_json::Value) -> api::channel::Channel {
+        serde_json::from_value(json!({
+            "id": "20",
+            "type": 0,
+            "permission_overwrites": overwrites,
+        }))
+        .unwrap()
+    }
+
+    fn overwrite(
+        id: u64,
+        member: bool,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> serde_json::Value {
+        json!({
+            "id": id.to_string(),
+            "type": if member { 1 } else { 0 },
+            "allow": allow.bits().to_string(),
+            "deny": deny.bits().to_string(),
+        })
+    }
+
+    #[test]
+    fn owner_has_every_permission() {
+        let guild = guild(Permissions::empty(), Permissions::empty());
+        let owner = member(OWNER_ID, None);
+        assert_eq!(guild_permissions(&guild, &owner), Permissions::all());
+
+        let everyone_denied = overwrite(GUILD_ID, false, Permissions::empty(), Permissions::all());
+        let channel = channel(json!([everyone_denied]));
+        assert_eq!(channel_permissions(&guild, &owner, &channel), Permissions::all());
+    }
+
+    #[test]
+    fn administrator_ignores_overwrites() {
+        let guild = guild(Permissions::VIEW_CHANNEL, Permissions::ADMINISTRATOR);
+        let admin = member(USER_ID, None);
+        assert_eq!(guild_permissions(&guild, &admin), Permissions::all());
+
+        let member_denied = overwrite(USER_ID, true, Permissions::empty(), Permissions::all());
+        let channel = channel(json!([member_denied]));
+        assert_eq!(channel_permissions(&guild, &admin, &channel), Permissions::all());
+    }
+
+    #[test]
+    fn member_overwrite_allows_after_role_deny() {
+        let everyone = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+        let guild = guild(everyone, Permissions::ATTACH_FILES);
+        let member = member(USER_ID, None);
+        assert_eq!(
+            guild_permissions(&guild, &member),
+            everyone | Permissions::ATTACH_FILES
+        );
+
+        let role_denied = Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES;
+        let channel = channel(json!([
+            overwrite(ROLE_ID, false, Permissions::empty(), role_denied),
+            overwrite(USER_ID, true, Permissions::SEND_MESSAGES, Permissions::empty()),
+        ]));
+        assert_eq!(
+            channel_permissions(&guild, &member, &channel),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+        );
+    }
+
+    #[test]
+    fn implicit_permissions_are_removed() {
+        let everyone = Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::EMBED_LINKS
+            | Permissions::READ_MESSAGE_HISTORY;
+        let guild = guild(everyone, Permissions::empty());
+        let member = member(USER_ID, None);
+
+        let denied = Permissions::SEND_MESSAGES;
+        let cant_send = overwrite(GUILD_ID, false, Permissions::empty(), denied);
+        assert_eq!(
+            channel_permissions(&guild, &member, &channel(json!([cant_send]))),
+            Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY
+        );
+
+        let cant_view = overwrite(GUILD_ID, false, Permissions::empty(), Permissions::VIEW_CHANNEL);
+        assert_eq!(
+            channel_permissions(&guild, &member, &channel(json!([cant_view]))),
+            Permissions::empty()
+        );
+    }
+
+    #[test]
+    fn timed_out_member_keeps_only_read_permissions() {
+        let everyone = Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::READ_MESSAGE_HISTORY;
+        let guild = guild(everyone, Permissions::KICK_MEMBERS);
+        let until = (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+        let member = member(USER_ID, Some(until));
+        assert_eq!(guild_permissions(&guild, &member), TIMED_OUT_PERMISSIONS);
+
+        let allowed = overwrite(USER_ID, true, Permissions::SEND_MESSAGES, Permissions::empty());
+        let channel = channel(json!([allowed]));
+        assert_eq!(channel_permissions(&guild, &member, &channel), TIMED_OUT_PERMISSIONS);
+
+        let expired = (chrono::Utc::now() - chrono::Duration::hours(1)).to_rfc3339();
+        let member = self::member(USER_ID, Some(expired));
+        assert_eq!(
+            guild_permissions(&guild, &member),
+            everyone | Permissions::KICK_MEMBERS
+        );
+    }
+}
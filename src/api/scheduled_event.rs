@@ -0,0 +1,257 @@
+use crate::api;
+
+use futures::stream::Stream;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum PrivacyLevel {
+    /// The event is only visible to members of the guild
+    GuildOnly = 2,
+}
+
+/// The lifecycle of an event. Scheduled events can become active or canceled, and active events
+/// can only become completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum EventStatus {
+    Scheduled = 1,
+    Active = 2,
+    Completed = 3,
+    Canceled = 4,
+}
+
+/// Where an event takes place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum EntityType {
+    StageInstance = 1,
+    Voice = 2,
+    /// Somewhere outside of Discord, which requires a location and an end time
+    External = 3,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EntityMetadata {
+    /// The location of an external event, up to 100 characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+/// An [event](https://discord.com/developers/docs/resources/guild-scheduled-event#guild-scheduled-event-object)
+/// that has been scheduled in a guild.
+#[derive(Debug, Deserialize)]
+pub struct GuildScheduledEvent {
+    pub id: api::misc::Snowflake,
+    pub guild_id: api::misc::Snowflake,
+    /// The channel the event takes place in, which is `None` for external events
+    pub channel_id: Option<api::misc::Snowflake>,
+    pub creator_id: Option<api::misc::Snowflake>,
+    pub name: String,
+    pub description: Option<String>,
+    /// ISO8601 timestamp of when the event starts
+    pub scheduled_start_time: String,
+    /// ISO8601 timestamp of when the event ends, which is required for external events
+    pub scheduled_end_time: Option<String>,
+    pub privacy_level: PrivacyLevel,
+    pub status: EventStatus,
+    pub entity_type: EntityType,
+    /// The ID of the stage instance of the event
+    pub entity_id: Option<api::misc::Snowflake>,
+    pub entity_metadata: Option<EntityMetadata>,
+    pub creator: Option<api::user::User>,
+    /// The number of users subscribed to the event, which is only sent when requested
+    pub user_count: Option<u32>,
+    /// The hash of the event's cover image
+    pub image: Option<String>,
+}
+
+/// Used to [create](https://discord.com/developers/docs/resources/guild-scheduled-event#create-guild-scheduled-event)
+/// or modify an event. Only the settings that have been set are sent to Discord.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ScheduledEventBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Option<api::misc::Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_metadata: Option<EntityMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_type: Option<EntityType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    privacy_level: Option<PrivacyLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_start_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_end_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<EventStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+}
+
+impl ScheduledEventBuilder {
+    /// Starts an event that is only visible to members of the guild. New events need a name, a
+    /// start time and a location, which is either a channel or an
+    /// [external location](ScheduledEventBuilder::external).
+    pub fn new() -> Self {
+        Self {
+            privacy_level: Some(PrivacyLevel::GuildOnly),
+            ..Self::default()
+        }
+    }
+
+    /// The name of the event, between 1 and 100 characters.
+    pub fn name(&mut self, name: String) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// A description of the event, up to 1000 characters.
+    pub fn description(&mut self, description: String) -> &mut Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Holds the event in a voice or stage channel.
+    pub fn channel(
+        &mut self,
+        channel_id: api::misc::Snowflake,
+        channel_type: EntityType,
+    ) -> &mut Self {
+        self.channel_id = Some(Some(channel_id));
+        self.entity_type = Some(channel_type);
+        self.entity_metadata = None;
+        self
+    }
+
+    /// Holds the event somewhere outside of Discord. External events also need an end time.
+    pub fn external(&mut self, location: String) -> &mut Self {
+        self.channel_id = Some(None);
+        self.entity_type = Some(EntityType::External);
+        self.entity_metadata = Some(EntityMetadata {
+            location: Some(location),
+        });
+        self
+    }
+
+    /// ISO8601 timestamp of when the event starts.
+    pub fn scheduled_start_time(&mut self, timestamp: String) -> &mut Self {
+        self.scheduled_start_time = Some(timestamp);
+        self
+    }
+
+    /// ISO8601 timestamp of when the event ends.
+    pub fn scheduled_end_time(&mut self, timestamp: String) -> &mut Self {
+        self.scheduled_end_time = Some(timestamp);
+        self
+    }
+
+    /// Starts, ends or cancels an existing event. This is ignored when creating an event.
+    pub fn status(&mut self, status: EventStatus) -> &mut Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// The event's cover image, as a data URI created with [`api::misc::image_data_uri`].
+    pub fn image(&mut self, image: String) -> &mut Self {
+        self.image = Some(image);
+        self
+    }
+}
+
+/// A user that has subscribed to an event.
+#[derive(Debug, Deserialize)]
+pub struct ScheduledEventUser {
+    pub guild_scheduled_event_id: api::misc::Snowflake,
+    pub user: api::user::User,
+    /// The user's membership in the guild, which is only sent when requested
+    pub member: Option<api::guild::GuildMember>,
+}
+
+/// Sent through the gateway when a user subscribes to or unsubscribes from an event.
+#[derive(Debug, Deserialize)]
+pub struct ScheduledEventUserEvent {
+    pub guild_scheduled_event_id: api::misc::Snowflake,
+    pub user_id: api::misc::Snowflake,
+    pub guild_id: api::misc::Snowflake,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ScheduledEventsQuery {
+    pub with_user_count: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ScheduledEventUsersQuery {
+    limit: u8,
+    with_member: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<api::misc::Snowflake>,
+}
+
+/// The most subscribers that can be fetched for an event in a single request
+const EVENT_USERS_PAGE_LIMIT: u8 = 100;
+
+impl GuildScheduledEvent {
+    fn endpoint(&self) -> String {
+        format!("guilds/{}/scheduled-events/{}", self.guild_id, self.id)
+    }
+
+    /// Changes the settings of the event, returning the updated event. This requires the
+    /// `MANAGE_EVENTS` permission.
+    pub async fn modify(
+        &self,
+        config: &api::config::BotConfig,
+        changes: &ScheduledEventBuilder,
+        reason: Option<String>,
+    ) -> Self {
+        api::base::patch(config, self.endpoint(), changes, reason).await
+    }
+
+    /// Deletes the event. This requires the `MANAGE_EVENTS` permission.
+    pub async fn delete(&self, config: &api::config::BotConfig, reason: Option<String>) {
+        api::base::delete(config, self.endpoint(), reason).await
+    }
+
+    /// Fetches a page of up to `limit` (at most 100) subscribers, ordered by user ID, starting
+    /// with the first subscriber whose ID is greater than `after`. When `with_member` is true,
+    /// each subscriber's guild membership is included.
+    pub async fn users(
+        &self,
+        config: &api::config::BotConfig,
+        after: Option<api::misc::Snowflake>,
+        limit: u8,
+        with_member: bool,
+    ) -> Vec<ScheduledEventUser> {
+        let query = ScheduledEventUsersQuery {
+            limit: limit.clamp(1, EVENT_USERS_PAGE_LIMIT),
+            with_member,
+            after,
+        };
+        let endpoint = format!("{}/users", self.endpoint());
+        api::base::get_with_query(config, endpoint, query).await
+    }
+
+    /// Streams every subscriber of the event, requesting pages of subscribers as the stream is
+    /// consumed.
+    pub fn all_users<'a>(
+        &'a self,
+        config: &'a api::config::BotConfig,
+        with_member: bool,
+    ) -> impl Stream<Item = ScheduledEventUser> + 'a {
+        api::base::paginate(
+            None,
+            move |after| self.users(config, after, EVENT_USERS_PAGE_LIMIT, with_member),
+            |_, page: &Vec<ScheduledEventUser>| match page.last() {
+                Some(last) if page.len() == EVENT_USERS_PAGE_LIMIT as usize => {
+                    Some(Some(last.user.id))
+                }
+                _ => None,
+            },
+        )
+    }
+}
@@ -0,0 +1,93 @@
+use crate::api;
+
+use serde::Deserialize;
+use serde_repr::Deserialize_repr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr)]
+#[repr(u8)]
+pub enum StickerType {
+    /// An official sticker in a pack
+    Standard = 1,
+    /// A sticker uploaded to a guild
+    Guild = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr)]
+#[repr(u8)]
+pub enum StickerFormatType {
+    Png = 1,
+    Apng = 2,
+    Lottie = 3,
+    Gif = 4,
+}
+
+/// A [sticker](https://discord.com/developers/docs/resources/sticker#sticker-object) that can be
+/// sent in messages.
+#[derive(Debug, Deserialize)]
+pub struct Sticker {
+    pub id: api::misc::Snowflake,
+    /// The pack of a standard sticker
+    pub pack_id: Option<api::misc::Snowflake>,
+    pub name: String,
+    pub description: Option<String>,
+    /// Autocomplete and suggestion tags for the sticker
+    pub tags: String,
+    #[serde(rename(deserialize = "type"))]
+    pub sticker_type: StickerType,
+    pub format_type: StickerFormatType,
+    /// Whether a guild sticker can be used, which may be false if the guild lost boosts
+    pub available: Option<bool>,
+    pub guild_id: Option<api::misc::Snowflake>,
+    /// The user that uploaded a guild sticker
+    pub user: Option<api::user::User>,
+    pub sort_value: Option<i64>,
+}
+
+/// Used to upload a new sticker to a guild with
+/// [`Guild::create_sticker`](api::guild::Guild::create_sticker).
+#[derive(Clone, Debug)]
+pub struct CreateStickerBuilder {
+    name: String,
+    description: String,
+    tags: String,
+    file: api::channel::AttachmentFile,
+}
+
+impl CreateStickerBuilder {
+    /// Starts a sticker with a name of 2 to 30 characters and the image in `file`, which must be
+    /// a PNG, APNG, GIF or Lottie JSON file of at most 512 KiB. The `tags` are the name of a
+    /// unicode emoji that's related to the sticker, which is used for suggestions.
+    pub fn new(name: String, tags: String, file: api::channel::AttachmentFile) -> Self {
+        Self {
+            name,
+            description: String::new(),
+            tags,
+            file,
+        }
+    }
+
+    /// A description of 2 to 100 characters.
+    pub fn description(&mut self, description: String) -> &mut Self {
+        self.description = description;
+        self
+    }
+
+    pub(crate) fn form_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("name", self.name.clone()),
+            ("description", self.description.clone()),
+            ("tags", self.tags.clone()),
+        ]
+    }
+
+    pub(crate) fn file(&self) -> (String, Vec<u8>) {
+        self.file.form_part()
+    }
+}
+
+/// Sent through the gateway when a guild's stickers change, with the full set of stickers.
+#[derive(Debug, Deserialize)]
+pub struct GuildStickersUpdate {
+    pub guild_id: api::misc::Snowflake,
+    pub stickers: Vec<Sticker>,
+}
@@ -0,0 +1,170 @@
+use crate::api;
+
+use serde::{Deserialize, Serialize};
+
+/// A user that has [joined](https://discord.com/developers/docs/resources/channel#thread-member-object)
+/// a thread.
+#[derive(Debug, Deserialize)]
+pub struct ThreadMember {
+    /// The ID of the thread. This is omitted from the members sent in `GUILD_CREATE`.
+    pub id: Option<api::misc::Snowflake>,
+    /// The ID of the user. This is omitted from the members sent in `GUILD_CREATE`.
+    pub user_id: Option<api::misc::Snowflake>,
+    /// ISO8601 timestamp of when the user last joined the thread
+    pub join_timestamp: String,
+    pub flags: u64,
+    pub member: Option<api::guild::GuildMember>,
+}
+
+/// Used to start a new thread, either from an existing message with
+/// [`Message::start_thread`](api::channel::Message::start_thread), on its own with
+/// [`Channel::start_thread`](api::channel::Channel::start_thread), or as a forum post with
+/// [`Channel::create_forum_post`](api::channel::Channel::create_forum_post).
+#[derive(Clone, Debug, Serialize)]
+pub struct StartThreadBuilder {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_archive_duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u16>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    thread_type: Option<api::channel::ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invitable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    applied_tags: Option<Vec<api::misc::Snowflake>>,
+}
+
+impl StartThreadBuilder {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            auto_archive_duration: None,
+            rate_limit_per_user: None,
+            thread_type: None,
+            invitable: None,
+            applied_tags: None,
+        }
+    }
+
+    /// The number of minutes of inactivity after which the thread is archived: 60, 1440, 4320
+    /// or 10080.
+    pub fn auto_archive_duration(&mut self, minutes: u32) -> &mut Self {
+        self.auto_archive_duration = Some(minutes);
+        self
+    }
+
+    /// The number of seconds users must wait between sending messages (slowmode), up to 21600.
+    pub fn rate_limit_per_user(&mut self, seconds: u16) -> &mut Self {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// The type of thread to create, which is only used for threads that aren't started from a
+    /// message. Defaults to a private thread.
+    pub fn thread_type(&mut self, thread_type: api::channel::ChannelType) -> &mut Self {
+        self.thread_type = Some(thread_type);
+        self
+    }
+
+    /// Whether non-moderators can add other non-moderators to a private thread.
+    pub fn invitable(&mut self, invitable: bool) -> &mut Self {
+        self.invitable = Some(invitable);
+        self
+    }
+
+    /// The IDs of the tags to apply to a post in a forum channel, up to 5.
+    pub fn applied_tags(&mut self, tag_ids: Vec<api::misc::Snowflake>) -> &mut Self {
+        self.applied_tags = Some(tag_ids);
+        self
+    }
+}
+
+/// A set of threads, along with the bot's thread member for each thread it has joined.
+#[derive(Debug, Deserialize)]
+pub struct ThreadList {
+    pub threads: Vec<api::channel::Channel>,
+    pub members: Vec<ThreadMember>,
+    /// Whether there are more threads that can be fetched. This is only sent for archived
+    /// threads.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl IntoIterator for ThreadList {
+    type Item = api::channel::Channel;
+    type IntoIter = std::vec::IntoIter<api::channel::Channel>;
+
+    /// Iterates over the threads in the list, leaving out the bot's thread members.
+    fn into_iter(self) -> Self::IntoIter {
+        self.threads.into_iter()
+    }
+}
+
+/// The different sets of archived threads that can be listed for a channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchivedThreads {
+    /// Public threads, ordered by when they were archived
+    Public,
+    /// Private threads, ordered by when they were archived. This requires `MANAGE_THREADS`.
+    Private,
+    /// Private threads that the bot has joined, ordered by their ID
+    JoinedPrivate,
+}
+
+impl ArchivedThreads {
+    pub(crate) fn endpoint(&self, channel_id: &str) -> String {
+        match self {
+            Self::Public => format!("channels/{}/threads/archived/public", channel_id),
+            Self::Private => format!("channels/{}/threads/archived/private", channel_id),
+            Self::JoinedPrivate => {
+                format!("channels/{}/users/@me/threads/archived/private", channel_id)
+            }
+        }
+    }
+
+    /// The value to pass as `before` to get the page of threads after `thread`, which is an
+    /// ISO8601 archive timestamp for most lists, and the thread's ID for joined private threads.
+    pub fn cursor(&self, thread: &api::channel::Channel) -> Option<String> {
+        match self {
+            Self::JoinedPrivate => Some(thread.id.clone()),
+            _ => thread
+                .thread_metadata
+                .as_ref()
+                .map(|metadata| metadata.archive_timestamp.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ArchivedThreadsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Sent through the gateway when the bot gains access to a channel, to sync the active threads
+/// within it.
+#[derive(Debug, Deserialize)]
+pub struct ThreadListSync {
+    pub guild_id: api::misc::Snowflake,
+    /// The parent channels whose threads are being synced. When omitted, the threads of the
+    /// entire guild are being synced.
+    pub channel_ids: Option<Vec<api::misc::Snowflake>>,
+    pub threads: Vec<api::channel::Channel>,
+    pub members: Vec<ThreadMember>,
+}
+
+/// Sent through the gateway when users are added to or removed from a thread.
+#[derive(Debug, Deserialize)]
+pub struct ThreadMembersUpdate {
+    pub id: api::misc::Snowflake,
+    pub guild_id: api::misc::Snowflake,
+    /// An approximate count of the users in the thread, which stops counting at 50
+    pub member_count: u32,
+    #[serde(default)]
+    pub added_members: Vec<ThreadMember>,
+    #[serde(default)]
+    pub removed_member_ids: Vec<api::misc::Snowflake>,
+}
@@ -22,7 +22,7 @@ pub enum Flag {
     BotHttpInteractions = 1 << 19,
 }
 
-#[derive(Debug, Deserialize_repr)]
+#[derive(Clone, Copy, Debug, Deserialize_repr)]
 #[repr(i64)]
 pub enum PremiumType {
     NoPremium = 0,
@@ -30,7 +30,7 @@ pub enum PremiumType {
     Nitro = 2
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct User {
     pub id: api::misc::Snowflake,
     pub username: String,
@@ -46,31 +46,67 @@ pub struct User {
 }
 
 impl User {
-    pub fn current(config: &api::config::BotConfig) -> Self {
-        api::base::get(config, String::from("users/@me"))
+    pub async fn current(config: &api::config::BotConfig) -> Self {
+        api::base::get(config, String::from("users/@me")).await
     }
 
-    pub fn get(config: &api::config::BotConfig, user_id: String) -> Self {
-        api::base::get(config, format!("users/{}", user_id))
+    pub async fn get(config: &api::config::BotConfig, user_id: String) -> Self {
+        api::base::get(config, format!("users/{}", user_id)).await
     }
 
-    pub fn dm_channel(&self, config: &api::config::BotConfig) -> api::channel::Channel {
+    pub async fn dm_channel(&self, config: &api::config::BotConfig) -> api::channel::Channel {
         let mut payload = HashMap::new();
-        payload.insert(String::from("recipient_id"), self.id.clone());
-        api::base::post(config, String::from("users/@me/channels"), payload)
+        payload.insert(String::from("recipient_id"), *self.id);
+        api::base::post(config, String::from("users/@me/channels"), payload, None).await
     }
 
     pub fn has_flag(&self, flag: Flag) -> Option<bool> {
         let flag_discrim = flag as u32;
-        if let Some(user_flags) = self.flags {
-            return Some((flag_discrim & user_flags) == flag_discrim)
-        } else { None }
+        self.flags.map(|user_flags| (flag_discrim & user_flags) == flag_discrim)
     }
 
     pub fn has_public_flag(&self, flag: Flag) -> Option<bool> {
         let flag_discrim = flag as u32;
-        if let Some(public_user_flags) = self.public_flags {
-            return Some((flag_discrim & public_user_flags) == flag_discrim)
-        } else { None }
+        self.public_flags.map(|public_flags| (flag_discrim & public_flags) == flag_discrim)
     }
 }
+
+/// A user that only contains their ID, as sent in presence updates.
+#[derive(Debug, Deserialize)]
+pub struct PartialUser {
+    pub id: api::misc::Snowflake,
+}
+
+/// Something a user is [doing](https://discord.com/developers/docs/topics/gateway-events#activity-object),
+/// such as playing a game or listening to music.
+#[derive(Debug, Deserialize)]
+pub struct Activity {
+    pub name: String,
+    #[serde(rename(deserialize = "type"))]
+    pub activity_type: i64,
+    pub url: Option<String>,
+    pub details: Option<String>,
+    pub state: Option<String>,
+}
+
+/// The [status](https://discord.com/developers/docs/topics/gateway-events#client-status-object)
+/// of a user on each platform they're active on.
+#[derive(Debug, Deserialize)]
+pub struct ClientStatus {
+    pub desktop: Option<String>,
+    pub mobile: Option<String>,
+    pub web: Option<String>,
+}
+
+/// The [presence](https://discord.com/developers/docs/topics/gateway-events#presence-update) of
+/// a user within a guild.
+#[derive(Debug, Deserialize)]
+pub struct Presence {
+    pub user: PartialUser,
+    pub guild_id: Option<api::misc::Snowflake>,
+    /// One of `idle`, `dnd`, `online` or `offline`
+    pub status: String,
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    pub client_status: Option<ClientStatus>,
+}
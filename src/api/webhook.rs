@@ -0,0 +1,362 @@
+use crate::api;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_repr::Deserialize_repr;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr)]
+#[repr(u8)]
+pub enum WebhookType {
+    /// A webhook that messages can be sent through with its token
+    Incoming = 1,
+    /// A webhook that posts messages from a followed announcement channel
+    ChannelFollower = 2,
+    /// A webhook used by an application to respond to interactions
+    Application = 3,
+}
+
+/// A [webhook](https://discord.com/developers/docs/resources/webhook#webhook-object), which can
+/// send messages to a channel without a bot user.
+#[derive(Debug, Deserialize)]
+pub struct Webhook {
+    pub id: api::misc::Snowflake,
+    #[serde(rename(deserialize = "type"))]
+    pub webhook_type: WebhookType,
+    pub guild_id: Option<api::misc::Snowflake>,
+    pub channel_id: Option<api::misc::Snowflake>,
+    /// The user that created the webhook, which isn't sent when the webhook is fetched with its
+    /// token
+    pub user: Option<api::user::User>,
+    /// The default username of messages sent by the webhook
+    pub name: Option<String>,
+    /// The default avatar hash of messages sent by the webhook
+    pub avatar: Option<String>,
+    /// The secret token used to execute the webhook, which is only sent for incoming webhooks
+    pub token: Option<String>,
+    pub application_id: Option<api::misc::Snowflake>,
+    /// The URL used to execute the webhook, which is only sent for incoming webhooks
+    pub url: Option<String>,
+}
+
+/// The reasons that a webhook URL couldn't be parsed with [`WebhookClient::from_url`].
+#[derive(Debug)]
+pub enum WebhookUrlError {
+    /// The URL isn't a valid URL
+    InvalidUrl(url::ParseError),
+    /// The URL doesn't contain a webhook ID followed by its token
+    MissingCredentials,
+}
+
+impl fmt::Display for WebhookUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl(error) => write!(f, "Invalid webhook URL: {}", error),
+            Self::MissingCredentials => {
+                write!(f, "Webhook URLs must end in `/webhooks/{{id}}/{{token}}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebhookUrlError {}
+
+/// Used to create a webhook with [`Channel::create_webhook`](api::channel::Channel::create_webhook),
+/// or [modify](https://discord.com/developers/docs/resources/webhook#modify-webhook) an existing
+/// one. Only the settings that have been set are sent to Discord.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct WebhookBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<api::misc::Snowflake>,
+}
+
+impl WebhookBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The default username of messages sent by the webhook, between 1 and 80 characters.
+    pub fn name(&mut self, name: String) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// The default avatar of messages sent by the webhook, as a data URI created with
+    /// [`api::misc::image_data_uri`], or `None` to remove the avatar.
+    pub fn avatar(&mut self, avatar: Option<String>) -> &mut Self {
+        self.avatar = Some(avatar);
+        self
+    }
+
+    /// Moves the webhook to another channel in the same guild. This is ignored when creating a
+    /// webhook.
+    pub fn channel_id(&mut self, channel_id: api::misc::Snowflake) -> &mut Self {
+        self.channel_id = Some(channel_id);
+        self
+    }
+}
+
+/// A message that will be sent through a webhook with [`WebhookClient::execute`].
+#[derive(Debug, Default, Serialize)]
+pub struct ExecuteWebhookBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<String>,
+    tts: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<api::message::MessageEmbed>,
+    #[serde(
+        rename(serialize = "attachments"),
+        serialize_with = "api::channel::serialize_attachments",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    files: Vec<api::channel::AttachmentFile>,
+    allowed_mentions: api::message::AllowedMentions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_name: Option<String>,
+    #[serde(skip)]
+    thread_id: Option<api::misc::Snowflake>,
+    #[serde(skip)]
+    wait: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ExecuteWebhookQuery {
+    wait: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<api::misc::Snowflake>,
+}
+
+#[derive(Debug, Serialize)]
+struct WebhookMessageQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<api::misc::Snowflake>,
+}
+
+impl ExecuteWebhookBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(content: String) -> Self {
+        let mut message = Self::new();
+        message.content = Some(content);
+        message
+    }
+
+    pub fn content(&mut self, content: String) -> &mut Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Overrides the webhook's default username for this message.
+    pub fn username(&mut self, username: String) -> &mut Self {
+        self.username = Some(username);
+        self
+    }
+
+    /// Overrides the webhook's default avatar for this message with the image at `avatar_url`.
+    pub fn avatar_url(&mut self, avatar_url: String) -> &mut Self {
+        self.avatar_url = Some(avatar_url);
+        self
+    }
+
+    pub fn tts(&mut self, tts: bool) -> &mut Self {
+        self.tts = tts;
+        self
+    }
+
+    /// Adds an embed to this message, with the same limits as when sending a message as the bot.
+    pub fn embed(
+        &mut self,
+        embed: api::message::MessageEmbed,
+    ) -> Result<&mut Self, api::message::EmbedLimitError> {
+        api::message::push_embed(&mut self.embeds, embed)?;
+        Ok(self)
+    }
+
+    /// Uploads a file alongside this message.
+    pub fn attach(&mut self, file: api::channel::AttachmentFile) -> &mut Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Which mentions in the message will notify their targets. Webhooks aren't executed with a
+    /// config, so this defaults to [`AllowedMentions::default`](api::message::AllowedMentions),
+    /// which only allows users to be pinged.
+    pub fn allowed_mentions(&mut self, mentions: api::message::AllowedMentions) -> &mut Self {
+        self.allowed_mentions = mentions;
+        self
+    }
+
+    /// Sends the message to a thread within the webhook's channel.
+    pub fn thread_id(&mut self, thread_id: api::misc::Snowflake) -> &mut Self {
+        self.thread_id = Some(thread_id);
+        self
+    }
+
+    /// Creates a post with this name when the webhook's channel is a forum channel.
+    pub fn thread_name(&mut self, thread_name: String) -> &mut Self {
+        self.thread_name = Some(thread_name);
+        self
+    }
+
+    /// Whether to wait for the message to be sent so that it can be returned. Without waiting,
+    /// there's no way to tell whether the message was sent successfully.
+    pub fn wait(&mut self, wait: bool) -> &mut Self {
+        self.wait = wait;
+        self
+    }
+}
+
+/// The ID and token of an incoming webhook, which is all that's needed to send messages through
+/// it. Executing a webhook doesn't need a bot token, so a client can be created from just the
+/// webhook's URL with [`WebhookClient::from_url`], or from a fetched webhook with
+/// [`Webhook::client`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookClient {
+    pub id: api::misc::Snowflake,
+    pub token: String,
+}
+
+impl Webhook {
+    /// Fetches a webhook by its ID. This requires the `MANAGE_WEBHOOKS` permission.
+    pub async fn get(config: &api::config::BotConfig, webhook_id: api::misc::Snowflake) -> Self {
+        api::base::get(config, format!("webhooks/{}", webhook_id)).await
+    }
+
+    /// Changes the settings of the webhook, returning the updated webhook. This requires the
+    /// `MANAGE_WEBHOOKS` permission.
+    pub async fn modify(
+        &self,
+        config: &api::config::BotConfig,
+        changes: &WebhookBuilder,
+        reason: Option<String>,
+    ) -> Self {
+        api::base::patch(config, format!("webhooks/{}", self.id), changes, reason).await
+    }
+
+    /// Deletes the webhook. This requires the `MANAGE_WEBHOOKS` permission.
+    pub async fn delete(&self, config: &api::config::BotConfig, reason: Option<String>) {
+        api::base::delete(config, format!("webhooks/{}", self.id), reason).await
+    }
+
+    /// A client for sending messages through the webhook, which is `None` when the webhook
+    /// doesn't have a token, such as channel follower webhooks.
+    pub fn client(&self) -> Option<WebhookClient> {
+        self.token.as_ref().map(|token| WebhookClient {
+            id: self.id,
+            token: token.clone(),
+        })
+    }
+}
+
+impl WebhookClient {
+    /// Creates a client from a webhook's URL, such as
+    /// `https://discord.com/api/webhooks/{id}/{token}`.
+    pub fn from_url(webhook_url: &str) -> Result<Self, WebhookUrlError> {
+        let parsed = url::Url::parse(webhook_url).map_err(WebhookUrlError::InvalidUrl)?;
+        let mut segments = parsed
+            .path_segments()
+            .ok_or(WebhookUrlError::MissingCredentials)?
+            .skip_while(|segment| *segment != "webhooks")
+            .skip(1);
+        let id = segments
+            .next()
+            .and_then(|id| id.parse::<u64>().ok())
+            .ok_or(WebhookUrlError::MissingCredentials)?;
+        let token = segments
+            .next()
+            .filter(|token| !token.is_empty())
+            .ok_or(WebhookUrlError::MissingCredentials)?;
+
+        Ok(Self {
+            id: api::misc::Snowflake::from(id),
+            token: token.to_string(),
+        })
+    }
+
+    /// The endpoint for executing the webhook, which is authenticated by the webhook's token
+    /// rather than the bot's.
+    fn endpoint(&self) -> String {
+        format!("webhooks/{}/{}", self.id, self.token)
+    }
+
+    /// Sends a message through the webhook. The sent message is only returned when the message
+    /// [waits](ExecuteWebhookBuilder::wait) for it to be sent.
+    pub async fn execute(
+        &self,
+        message: &ExecuteWebhookBuilder,
+    ) -> Option<api::channel::Message> {
+        let query = ExecuteWebhookQuery {
+            wait: message.wait,
+            thread_id: message.thread_id,
+        };
+        let endpoint = self.endpoint();
+        if message.files.is_empty() {
+            return api::base::webhook_json(Method::POST, endpoint, query, message).await;
+        }
+        let files = message
+            .files
+            .iter()
+            .map(api::channel::AttachmentFile::form_part)
+            .collect();
+        api::base::webhook_multipart(Method::POST, endpoint, query, message, files).await
+    }
+
+    /// Fetches a message that was sent by the webhook.
+    pub async fn get_message(
+        &self,
+        message_id: api::misc::Snowflake,
+        thread_id: Option<api::misc::Snowflake>,
+    ) -> api::channel::Message {
+        let endpoint = format!("{}/messages/{}", self.endpoint(), message_id);
+        let query = WebhookMessageQuery { thread_id };
+        api::base::webhook_empty(Method::GET, endpoint, query).await
+    }
+
+    /// Edits a message that was sent by the webhook, returning the updated message. As with
+    /// [`Message::edit`](api::channel::Message::edit), the message's existing attachments are
+    /// kept unless only some of them are retained, which means fetching the message first when
+    /// new files are uploaded.
+    pub async fn edit_message(
+        &self,
+        message_id: api::misc::Snowflake,
+        message: &api::channel::EditMessageBuilder,
+        thread_id: Option<api::misc::Snowflake>,
+    ) -> api::channel::Message {
+        let existing: Vec<_> = if message.keeps_existing_attachments() {
+            let current = self.get_message(message_id, thread_id).await;
+            current.attachments.iter().map(|attachment| attachment.id).collect()
+        } else {
+            Vec::new()
+        };
+
+        let endpoint = format!("{}/messages/{}", self.endpoint(), message_id);
+        let query = WebhookMessageQuery { thread_id };
+        let payload = message.payload(&existing);
+        let files = message.file_parts();
+        if files.is_empty() {
+            return api::base::webhook_json(Method::PATCH, endpoint, query, payload).await;
+        }
+        api::base::webhook_multipart(Method::PATCH, endpoint, query, payload, files).await
+    }
+
+    /// Deletes a message that was sent by the webhook.
+    pub async fn delete_message(
+        &self,
+        message_id: api::misc::Snowflake,
+        thread_id: Option<api::misc::Snowflake>,
+    ) {
+        let endpoint = format!("{}/messages/{}", self.endpoint(), message_id);
+        let query = WebhookMessageQuery { thread_id };
+        api::base::webhook_empty(Method::DELETE, endpoint, query).await
+    }
+}
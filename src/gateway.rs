@@ -27,7 +27,7 @@ struct RawSendableGatewayMessage<T> {
 pub trait SendableGatewayMessage {
     fn opcode(&self) -> u32;
 
-    fn send(&self, websocket: &mut GatewayWebSocket) -> ()
+    fn send(&self, websocket: &mut GatewayWebSocket)
     where
         Self: Serialize,
     {
@@ -37,8 +37,6 @@ pub trait SendableGatewayMessage {
         };
         let formatted_payload = Message::Text(serde_json::to_string(&message).unwrap());
         websocket.write_message(formatted_payload).unwrap();
-
-        ();
     }
 }
 
@@ -58,7 +56,7 @@ trait ExpectableWebsocketMessage<T: std::fmt::Debug + de::DeserializeOwned> {
     fn expect_from_websocket(ws: &mut GatewayWebSocket) -> T {
         let raw_message = ws.read_message().unwrap().to_string();
         let parsed_message: PrivateGatewayEvent<T> = serde_json::from_str(&raw_message).unwrap();
-        return parsed_message.d;
+        parsed_message.d
     }
 }
 
@@ -151,31 +149,130 @@ pub struct HeartbeatAck {}
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 /// The different types of messages that should be expected from the user.
 ////////////////////////////////////////////////////////////////////////////////////////////////////
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum GatewayMessageData {
     HeartbeatAck(HeartbeatAck),
     GuildCreate(api::guild::Guild),
+    /// Sent instead of `GuildCreate` for guilds that are unavailable due to an outage
+    GuildUnavailable(api::guild::UnavailableGuild),
     MessageCreate(api::channel::Message),
+    GuildMemberAdd(api::guild::GuildMemberAdd),
     GuildMemberUpdate(api::guild::GuildMemberUpdate),
     InteractionCreate(api::interaction::Interaction),
+    MessageReactionAdd(api::channel::MessageReactionEvent),
+    MessageReactionRemove(api::channel::MessageReactionEvent),
+    ThreadCreate(api::channel::Channel),
+    ThreadUpdate(api::channel::Channel),
+    /// Only the `id`, `guild_id`, `parent_id` and `type` of the deleted thread are sent
+    ThreadDelete(api::channel::Channel),
+    ThreadListSync(api::thread::ThreadListSync),
+    ThreadMembersUpdate(api::thread::ThreadMembersUpdate),
+    GuildBanAdd(api::guild::GuildBanEvent),
+    GuildBanRemove(api::guild::GuildBanEvent),
+    GuildRoleCreate(api::guild::GuildRoleEvent),
+    GuildRoleUpdate(api::guild::GuildRoleEvent),
+    GuildRoleDelete(api::guild::GuildRoleDelete),
+    /// This requires the `VIEW_AUDIT_LOG` permission and the `GUILD_MODERATION` intent
+    GuildAuditLogEntryCreate(api::audit_log::AuditLogEntry),
+    InviteCreate(api::invite::InviteCreate),
+    InviteDelete(api::invite::InviteDelete),
+    GuildEmojisUpdate(api::emoji::GuildEmojisUpdate),
+    GuildStickersUpdate(api::sticker::GuildStickersUpdate),
+    GuildScheduledEventCreate(api::scheduled_event::GuildScheduledEvent),
+    GuildScheduledEventUpdate(api::scheduled_event::GuildScheduledEvent),
+    GuildScheduledEventDelete(api::scheduled_event::GuildScheduledEvent),
+    GuildScheduledEventUserAdd(api::scheduled_event::ScheduledEventUserEvent),
+    GuildScheduledEventUserRemove(api::scheduled_event::ScheduledEventUserEvent),
+    /// Any other event, along with its unparsed data. `t` is `None` for events that aren't
+    /// dispatches.
+    Unknown {
+        t: Option<String>,
+        d: serde_json::Value,
+    },
 }
 
-#[derive(Debug, Deserialize)]
+impl GatewayMessageData {
+    /// Several events share the same shape, so the data of each event is parsed based on the
+    /// dispatch type that accompanies it.
+    fn from_dispatch(
+        opcode: i64,
+        dispatch_type: Option<&str>,
+        data: serde_json::Value,
+    ) -> serde_json::Result<Self> {
+        use serde_json::from_value;
+
+        Ok(match dispatch_type {
+            // Unavailable guilds only include their ID, so they can't be parsed as a `Guild`
+            Some("GUILD_CREATE") if data["unavailable"] == true => {
+                Self::GuildUnavailable(from_value(data)?)
+            }
+            Some("GUILD_CREATE") => Self::GuildCreate(from_value(data)?),
+            Some("MESSAGE_CREATE") => Self::MessageCreate(from_value(data)?),
+            Some("GUILD_MEMBER_ADD") => Self::GuildMemberAdd(from_value(data)?),
+            Some("GUILD_MEMBER_UPDATE") => Self::GuildMemberUpdate(from_value(data)?),
+            Some("INTERACTION_CREATE") => Self::InteractionCreate(from_value(data)?),
+            Some("MESSAGE_REACTION_ADD") => Self::MessageReactionAdd(from_value(data)?),
+            Some("MESSAGE_REACTION_REMOVE") => Self::MessageReactionRemove(from_value(data)?),
+            Some("THREAD_CREATE") => Self::ThreadCreate(from_value(data)?),
+            Some("THREAD_UPDATE") => Self::ThreadUpdate(from_value(data)?),
+            Some("THREAD_DELETE") => Self::ThreadDelete(from_value(data)?),
+            Some("THREAD_LIST_SYNC") => Self::ThreadListSync(from_value(data)?),
+            Some("THREAD_MEMBERS_UPDATE") => Self::ThreadMembersUpdate(from_value(data)?),
+            Some("GUILD_BAN_ADD") => Self::GuildBanAdd(from_value(data)?),
+            Some("GUILD_BAN_REMOVE") => Self::GuildBanRemove(from_value(data)?),
+            Some("GUILD_ROLE_CREATE") => Self::GuildRoleCreate(from_value(data)?),
+            Some("GUILD_ROLE_UPDATE") => Self::GuildRoleUpdate(from_value(data)?),
+            Some("GUILD_ROLE_DELETE") => Self::GuildRoleDelete(from_value(data)?),
+            Some("GUILD_AUDIT_LOG_ENTRY_CREATE") => {
+                Self::GuildAuditLogEntryCreate(from_value(data)?)
+            }
+            Some("INVITE_CREATE") => Self::InviteCreate(from_value(data)?),
+            Some("INVITE_DELETE") => Self::InviteDelete(from_value(data)?),
+            Some("GUILD_EMOJIS_UPDATE") => Self::GuildEmojisUpdate(from_value(data)?),
+            Some("GUILD_STICKERS_UPDATE") => Self::GuildStickersUpdate(from_value(data)?),
+            Some("GUILD_SCHEDULED_EVENT_CREATE") => {
+                Self::GuildScheduledEventCreate(from_value(data)?)
+            }
+            Some("GUILD_SCHEDULED_EVENT_UPDATE") => {
+                Self::GuildScheduledEventUpdate(from_value(data)?)
+            }
+            Some("GUILD_SCHEDULED_EVENT_DELETE") => {
+                Self::GuildScheduledEventDelete(from_value(data)?)
+            }
+            Some("GUILD_SCHEDULED_EVENT_USER_ADD") => {
+                Self::GuildScheduledEventUserAdd(from_value(data)?)
+            }
+            Some("GUILD_SCHEDULED_EVENT_USER_REMOVE") => {
+                Self::GuildScheduledEventUserRemove(from_value(data)?)
+            }
+            None if opcode == 11 => Self::HeartbeatAck(HeartbeatAck {}),
+            _ => Self::Unknown {
+                t: dispatch_type.map(String::from),
+                d: data,
+            },
+        })
+    }
+}
+
+#[derive(Debug)]
 pub struct GatewayEvent {
-    #[serde(rename(deserialize = "op"))]
     pub opcode: i64,
-
-    #[serde(rename(deserialize = "s"))]
     pub sequence_number: Option<i64>,
-
-    #[serde(rename(deserialize = "t"))]
     pub dispatch_type: Option<String>,
-
-    #[serde(rename(deserialize = "d"))]
     pub data: GatewayMessageData,
 }
 
+impl<'de> Deserialize<'de> for GatewayEvent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let raw: PrivateGatewayEvent<serde_json::Value> = Deserialize::deserialize(deserializer)?;
+        GatewayEvent::from_raw(raw).map_err(de::Error::custom)
+    }
+}
+
 // And this is what we'll construct after connecting to the websocket
 #[derive(Debug)]
 pub struct GatewayConnection {
@@ -191,17 +288,42 @@ impl GatewayConnection {
 }
 
 impl GatewayEvent {
+    fn from_raw(raw: PrivateGatewayEvent<serde_json::Value>) -> serde_json::Result<Self> {
+        let data = GatewayMessageData::from_dispatch(raw.op, raw.t.as_deref(), raw.d)?;
+        Ok(GatewayEvent {
+            opcode: raw.op,
+            sequence_number: raw.s,
+            dispatch_type: raw.t,
+            data,
+        })
+    }
+
+    /// Reads the next event from the gateway. The connection's sequence number is updated before
+    /// the event's data is parsed, so it stays current even when the data can't be parsed.
     pub fn from_gateway(conn: &mut GatewayConnection) -> Option<Self> {
         // TODO: This doesn't handle errors when reading from the websocket
         let raw_message = conn.websocket.read_message().unwrap().to_string();
-        let parsed_message = serde_json::from_str(&raw_message);
-        match parsed_message {
-            Ok(r) => return Some(r),
-            _ => {
-                println!("Error deserializing gateway message: {:#?}", raw_message);
+        let parsed: serde_json::Result<PrivateGatewayEvent<serde_json::Value>> =
+            serde_json::from_str(&raw_message);
+        let raw = match parsed {
+            Ok(raw) => raw,
+            Err(error) => {
+                println!("Error deserializing gateway message: {}", error);
                 return None;
             }
         };
+        if let Some(sequence_number) = raw.s {
+            conn.sequence_number.fetch_max(sequence_number, Ordering::Relaxed);
+        }
+
+        let dispatch_type = raw.t.clone();
+        match Self::from_raw(raw) {
+            Ok(event) => Some(event),
+            Err(error) => {
+                println!("Error parsing {:?} gateway event: {}", dispatch_type, error);
+                None
+            }
+        }
     }
 }
 
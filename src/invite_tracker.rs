@@ -0,0 +1,325 @@
+//! Attributes members joining a guild to the invite they used.
+//!
+//! Discord doesn't say which invite a member joined with, so the tracker keeps a snapshot of how
+//! many times each of a guild's invites has been used, and when a member joins it fetches the
+//! invites again to find the one whose use count went up. Tracking is opt-in: feed every gateway
+//! event to [`InviteTracker::handle`] and act on the joins it returns.
+//!
+//! ```ignore
+//! let mut tracker = InviteTracker::new();
+//! if let Some(join) = tracker.handle(&config, &event.data).await {
+//!     println!("{:?}", join);
+//! }
+//! ```
+//!
+//! The bot needs the `MANAGE_GUILD` permission in each tracked guild, along with the
+//! `GUILD_MEMBERS` and `GUILD_INVITES` intents. Guilds whose invites can't be fetched, such as
+//! those where the bot is missing the permission, aren't tracked.
+use crate::api;
+use crate::gateway::GatewayMessageData;
+
+use std::collections::HashMap;
+
+/// How a member that joined a guild was invited.
+#[derive(Debug)]
+pub enum InviteTrackerEvent {
+    MemberJoinedViaInvite {
+        member: api::guild::GuildMemberAdd,
+        invite_code: String,
+        /// The user that created the invite, which is `None` for invites created by widgets
+        inviter: Option<api::user::User>,
+    },
+    /// The member joined with the guild's vanity invite
+    MemberJoinedViaVanityUrl { member: api::guild::GuildMemberAdd },
+    /// The invite couldn't be determined, such as when the member was added through OAuth2 or
+    /// when several members joined at once
+    MemberJoinedViaUnknown { member: api::guild::GuildMemberAdd },
+}
+
+/// The state of an invite when it was last seen.
+#[derive(Debug)]
+struct TrackedInvite {
+    uses: u32,
+    /// The number of times the invite can be used, where 0 is unlimited
+    max_uses: u32,
+    inviter: Option<api::user::User>,
+}
+
+impl TrackedInvite {
+    /// Whether the invite will be deleted the next time it is used.
+    fn has_one_use_left(&self) -> bool {
+        self.max_uses != 0 && self.uses + 1 == self.max_uses
+    }
+}
+
+impl From<&api::invite::Invite> for TrackedInvite {
+    fn from(invite: &api::invite::Invite) -> Self {
+        Self {
+            uses: invite.uses.unwrap_or(0),
+            max_uses: invite.max_uses.unwrap_or(0),
+            inviter: invite.inviter.clone(),
+        }
+    }
+}
+
+/// What a member's join was attributed to after comparing the guild's invites.
+#[derive(Debug, PartialEq, Eq)]
+enum UsedInvite {
+    Invite(String),
+    Vanity,
+    Unknown,
+}
+
+/// Compares a guild's invites from before and after a member joined, along with how many times
+/// the vanity invite was used in between. The join is only attributed when exactly one use was
+/// added across all of them, which is either a single invite used once, an invite that vanished
+/// on its last use, or the vanity invite.
+fn used_invite(
+    previous: &HashMap<String, TrackedInvite>,
+    current: &HashMap<String, TrackedInvite>,
+    vanity_uses: u32,
+) -> UsedInvite {
+    let mut used: Vec<(&String, u32)> = current
+        .iter()
+        .filter_map(|(code, invite)| {
+            let before = previous.get(code).map_or(0, |tracked| tracked.uses);
+            let uses = invite.uses.saturating_sub(before);
+            (uses > 0).then_some((code, uses))
+        })
+        .collect();
+    // Invites are deleted as soon as they reach their max uses, which can happen before they
+    // are fetched again
+    used.extend(
+        previous
+            .iter()
+            .filter(|(code, tracked)| !current.contains_key(*code) && tracked.has_one_use_left())
+            .map(|(code, _)| (code, 1)),
+    );
+
+    match (used.as_slice(), vanity_uses) {
+        ([(code, 1)], 0) => UsedInvite::Invite(code.to_string()),
+        ([], 1) => UsedInvite::Vanity,
+        _ => UsedInvite::Unknown,
+    }
+}
+
+/// The invites of a single guild, along with the uses of its vanity invite if it has one.
+#[derive(Debug, Default)]
+struct GuildInvites {
+    invites: HashMap<String, TrackedInvite>,
+    vanity_uses: Option<u32>,
+}
+
+#[derive(Debug, Default)]
+pub struct InviteTracker {
+    guilds: HashMap<api::misc::Snowflake, GuildInvites>,
+}
+
+impl InviteTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the tracked invites from a gateway event. When the event is a member joining a
+    /// tracked guild, this returns how the member was invited.
+    pub async fn handle(
+        &mut self,
+        config: &api::config::BotConfig,
+        event: &GatewayMessageData,
+    ) -> Option<InviteTrackerEvent> {
+        match event {
+            GatewayMessageData::GuildCreate(guild) => {
+                // A guild that can't be snapshotted is left untracked
+                let _ = self.snapshot(config, guild).await;
+                None
+            }
+            GatewayMessageData::InviteCreate(invite) => {
+                self.invite_created(invite);
+                None
+            }
+            GatewayMessageData::InviteDelete(invite) => {
+                self.invite_deleted(invite);
+                None
+            }
+            GatewayMessageData::GuildMemberAdd(member) => {
+                self.member_joined(config, member.clone()).await
+            }
+            _ => None,
+        }
+    }
+
+    /// Starts tracking a guild, replacing any invites that were tracked for it before. When the
+    /// guild's invites can't be fetched, the guild stops being tracked and the error is returned.
+    pub async fn snapshot(
+        &mut self,
+        config: &api::config::BotConfig,
+        guild: &api::guild::Guild,
+    ) -> Result<(), api::misc::RequestError> {
+        let invites = match api::invite::Invite::try_list_for_guild(config, guild.id).await {
+            Ok(invites) => invites,
+            Err(error) => {
+                self.guilds.remove(&guild.id);
+                return Err(error);
+            }
+        };
+        let invites = invites
+            .iter()
+            .map(|invite| (invite.code.clone(), TrackedInvite::from(invite)))
+            .collect();
+        // Joins can still be attributed to regular invites without the vanity invite's uses
+        let vanity_uses = match guild.vanity_url_code {
+            Some(_) => api::guild::VanityUrl::try_get(config, guild.id)
+                .await
+                .ok()
+                .map(|vanity| vanity.uses),
+            None => None,
+        };
+        self.guilds.insert(guild.id, GuildInvites { invites, vanity_uses });
+        Ok(())
+    }
+
+    fn invite_created(&mut self, invite: &api::invite::InviteCreate) {
+        let guild_id = match invite.guild_id {
+            Some(guild_id) => guild_id,
+            None => return,
+        };
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            let tracked = TrackedInvite {
+                uses: invite.uses,
+                max_uses: invite.max_uses,
+                inviter: invite.inviter.clone(),
+            };
+            guild.invites.insert(invite.code.clone(), tracked);
+        }
+    }
+
+    fn invite_deleted(&mut self, invite: &api::invite::InviteDelete) {
+        if let Some(guild) = invite.guild_id.and_then(|guild_id| self.guilds.get_mut(&guild_id)) {
+            guild.invites.remove(&invite.code);
+        }
+    }
+
+    async fn member_joined(
+        &mut self,
+        config: &api::config::BotConfig,
+        member: api::guild::GuildMemberAdd,
+    ) -> Option<InviteTrackerEvent> {
+        let guild = self.guilds.get_mut(&member.guild_id)?;
+        let fetched = api::invite::Invite::try_list_for_guild(config, member.guild_id).await;
+        let invites = match fetched {
+            Ok(invites) => invites,
+            Err(_) => {
+                // The bot has most likely lost access to the guild's invites
+                self.guilds.remove(&member.guild_id);
+                return Some(InviteTrackerEvent::MemberJoinedViaUnknown { member });
+            }
+        };
+        let vanity_uses = match guild.vanity_uses {
+            Some(before) => {
+                let vanity = api::guild::VanityUrl::try_get(config, member.guild_id).await.ok();
+                guild.vanity_uses = vanity.map(|vanity| vanity.uses);
+                guild.vanity_uses.map_or(0, |uses| uses.saturating_sub(before))
+            }
+            None => 0,
+        };
+        let current = invites
+            .iter()
+            .map(|invite| (invite.code.clone(), TrackedInvite::from(invite)))
+            .collect();
+        let previous = std::mem::replace(&mut guild.invites, current);
+
+        let event = match used_invite(&previous, &guild.invites, vanity_uses) {
+            UsedInvite::Invite(code) => {
+                let inviter = guild
+                    .invites
+                    .get(&code)
+                    .or_else(|| previous.get(&code))
+                    .and_then(|tracked| tracked.inviter.clone());
+                InviteTrackerEvent::MemberJoinedViaInvite { member, invite_code: code, inviter }
+            }
+            UsedInvite::Vanity => InviteTrackerEvent::MemberJoinedViaVanityUrl { member },
+            UsedInvite::Unknown => InviteTrackerEvent::MemberJoinedViaUnknown { member },
+        };
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invite(uses: u32, max_uses: u32) -> TrackedInvite {
+        TrackedInvite { uses, max_uses, inviter: None }
+    }
+
+    fn invites(invites: Vec<(&str, TrackedInvite)>) -> HashMap<String, TrackedInvite> {
+        invites.into_iter().map(|(code, invite)| (code.to_string(), invite)).collect()
+    }
+
+    #[test]
+    fn single_use() {
+        let previous = invites(vec![("a", invite(1, 0)), ("b", invite(5, 10))]);
+        let current = invites(vec![("a", invite(2, 0)), ("b", invite(5, 10))]);
+        assert_eq!(used_invite(&previous, &current, 0), UsedInvite::Invite("a".to_string()));
+    }
+
+    #[test]
+    fn new_invite_used_once() {
+        let current = invites(vec![("a", invite(1, 0))]);
+        assert_eq!(used_invite(&HashMap::new(), &current, 0), UsedInvite::Invite("a".to_string()));
+    }
+
+    #[test]
+    fn several_invites_used() {
+        let previous = invites(vec![("a", invite(1, 0)), ("b", invite(5, 10))]);
+        let current = invites(vec![("a", invite(2, 0)), ("b", invite(6, 10))]);
+        assert_eq!(used_invite(&previous, &current, 0), UsedInvite::Unknown);
+
+        let current = invites(vec![("a", invite(3, 0)), ("b", invite(5, 10))]);
+        assert_eq!(used_invite(&previous, &current, 0), UsedInvite::Unknown);
+    }
+
+    #[test]
+    fn vanity_join() {
+        let previous = invites(vec![("a", invite(1, 0))]);
+        let current = invites(vec![("a", invite(1, 0))]);
+        assert_eq!(used_invite(&previous, &current, 1), UsedInvite::Vanity);
+
+        let current = invites(vec![("a", invite(2, 0))]);
+        assert_eq!(used_invite(&previous, &current, 1), UsedInvite::Unknown);
+    }
+
+    #[test]
+    fn exhausted_invite() {
+        let previous = invites(vec![("a", invite(1, 0)), ("b", invite(4, 5))]);
+        let current = invites(vec![("a", invite(1, 0))]);
+        assert_eq!(used_invite(&previous, &current, 0), UsedInvite::Invite("b".to_string()));
+    }
+
+    #[test]
+    fn deleted_invite() {
+        // Invites with more than one use left vanish because they were deleted, not used
+        let previous = invites(vec![("a", invite(1, 0)), ("b", invite(3, 5))]);
+        let current = invites(vec![("a", invite(1, 0))]);
+        assert_eq!(used_invite(&previous, &current, 0), UsedInvite::Unknown);
+
+        // Deleted invites are dropped from the cache, even with one use left
+        let mut tracker = InviteTracker::new();
+        let guild = GuildInvites {
+            invites: invites(vec![("a", invite(1, 0)), ("b", invite(4, 5))]),
+            vanity_uses: None,
+        };
+        tracker.guilds.insert(1.into(), guild);
+        let deleted: api::invite::InviteDelete = serde_json::from_value(serde_json::json!({
+            "channel_id": "2",
+            "guild_id": "1",
+            "code": "b",
+        }))
+        .unwrap();
+        tracker.invite_deleted(&deleted);
+
+        let previous = &tracker.guilds[&1.into()].invites;
+        let current = invites(vec![("a", invite(1, 0))]);
+        assert_eq!(used_invite(previous, &current, 0), UsedInvite::Unknown);
+    }
+}
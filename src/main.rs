@@ -1,52 +1,85 @@
-use std::sync::atomic;
 use std::{fs, io};
+use tokio::sync::mpsc;
+use tokio::task;
 
 pub mod api;
 pub mod gateway;
+pub mod invite_tracker;
 
-fn listen_for_messages(connection: &mut gateway::GatewayConnection) -> () {
+/// Reads events from the gateway and passes them on to be handled. The websocket blocks while
+/// waiting for the next event, so this runs on its own thread rather than on the async runtime.
+fn listen_for_messages(
+    mut connection: gateway::GatewayConnection,
+    events: mpsc::UnboundedSender<gateway::GatewayEvent>,
+) {
     loop {
         if let Some(next_message) = connection.read_event() {
-            // Update the sequence number on each message
-            if let Some(seq_num) = next_message.sequence_number {
-                println!("Updating seq num to {:#?}", seq_num);
-                connection
-                    .sequence_number
-                    .fetch_max(seq_num, atomic::Ordering::Relaxed);
+            // Stop listening once nothing is handling events anymore
+            if events.send(next_message).is_err() {
+                return;
             }
+        }
+    }
+}
 
-            // Handle the message data
-            match next_message.data {
-                gateway::GatewayMessageData::HeartbeatAck(_) => {
-                    println!("Received HeartbeatAck");
-                }
-                gateway::GatewayMessageData::GuildCreate(guild) => {
-                    println!("Guild info for guild {}", guild.name);
-                }
-                gateway::GatewayMessageData::MessageCreate(message) => {
-                    println!("Message created by {:#?}", message.author.username);
-                }
-                gateway::GatewayMessageData::InteractionCreate(interaction) => {
-                    println!("Received interaction: {:#?}", interaction)
-                }
-                gateway::GatewayMessageData::GuildMemberUpdate(update) => {
-                    println!(
-                        "Update for guild member `{}` in guild `{}`",
-                        update.user.username, update.guild_id
-                    );
-                }
-            }
+/// Handles a single event from the gateway. This runs on the async runtime, so handlers can
+/// `.await` API requests.
+async fn handle_event(next_message: gateway::GatewayEvent) {
+    match next_message.data {
+        gateway::GatewayMessageData::HeartbeatAck(_) => {
+            println!("Received HeartbeatAck");
+        }
+        gateway::GatewayMessageData::GuildCreate(guild) => {
+            println!("Guild info for guild {}", guild.name);
+        }
+        gateway::GatewayMessageData::MessageCreate(message) => {
+            println!("Message created by {:#?}", message.author.username);
+        }
+        gateway::GatewayMessageData::InteractionCreate(interaction) => {
+            println!("Received interaction: {:#?}", interaction)
+        }
+        gateway::GatewayMessageData::GuildMemberUpdate(update) => {
+            println!(
+                "Update for guild member `{}` in guild `{}`",
+                update.user.username, update.guild_id
+            );
+        }
+        gateway::GatewayMessageData::MessageReactionAdd(reaction) => {
+            println!(
+                "User `{}` reacted to message `{}`",
+                reaction.user_id, reaction.message_id
+            );
+        }
+        gateway::GatewayMessageData::MessageReactionRemove(reaction) => {
+            println!(
+                "User `{}` removed a reaction from message `{}`",
+                reaction.user_id, reaction.message_id
+            );
+        }
+        _ => {
+            println!("Received {:?} event", next_message.dispatch_type);
         }
     }
 }
 
-fn main() -> io::Result<()> {
+#[tokio::main]
+async fn main() -> io::Result<()> {
     let config_file_path = fs::canonicalize("./config/config.json")?;
     let bot_config = api::config::load_config(config_file_path)?;
 
-    let gateway_config = api::misc::BotGateway::get(&bot_config);
-    let mut connection = gateway::connect_to_gateway(&bot_config, gateway_config);
-    listen_for_messages(&mut connection);
+    let gateway_config = api::misc::BotGateway::get(&bot_config).await;
+    let connect_config = bot_config.clone();
+    let connection = task::spawn_blocking(move || {
+        gateway::connect_to_gateway(&connect_config, gateway_config)
+    })
+    .await
+    .expect("Gateway connection task panicked");
+
+    let (sender, mut events) = mpsc::unbounded_channel();
+    task::spawn_blocking(move || listen_for_messages(connection, sender));
+    while let Some(event) = events.recv().await {
+        handle_event(event).await;
+    }
 
-    return Ok(());
+    Ok(())
 }